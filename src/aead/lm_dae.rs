@@ -0,0 +1,144 @@
+use crate::aead::{from_nibbles, pad_10, to_nibbles, verify_tag, Aead, AuthenticationError};
+use crate::ciphers::skinnyee::{SKINNYee, SKINNYeeKey};
+use crate::ciphers::TweakableBlockCipher;
+use crate::matrix::Matrix;
+
+// Domain separation nibble, first cell of TK1.
+const HEADER: u8 = 0x0;
+const HEADER_FINAL: u8 = 0x1;
+const HEADER_FINAL_PADDED: u8 = 0x2;
+const MESSAGE: u8 = 0x4;
+const MESSAGE_FINAL_PADDED: u8 = 0x5;
+const TAG: u8 = 0x8;
+const ENCRYPTION: u8 = 0xC;
+
+const BLOCK_LEN: usize = 8;
+const NONCE_LEN: usize = 8;
+// Bytes of public data carried by TK2, TK3 and TK4.
+const TWEAK_DATA_LEN: usize = 24;
+// The key of the mode is 128 bits, so the 3-bit round constant seed of SKINNYee is a fixed
+// public value rather than key material.
+const RC_INIT: u8 = 0;
+
+/// Low-memory deterministic AEAD on SKINNYee, MAC-then-encrypt. The only secret is the 128-bit
+/// key, every public input goes through the tweak: TK1 holds the domain nibble and a 60-bit
+/// counter, TK2 to TK4 a chunk of `nonce || ad` (or the tag in the encryption phase). The
+/// message is CBC-MACed in the 64-bit state after the header, the tag (64-bit) is the last
+/// state and the key stream block `i` is `E_K^(i, tag)(tag)`.
+#[derive(Default)]
+pub struct LmDae {
+    skinny: SKINNYee,
+}
+
+impl LmDae {
+    /// 32 nibbles of key for K0 to K3 and the fixed seed `RC_INIT`: SKINNYee keys have 131 bits,
+    /// LM-DAE only uses 128 of them.
+    fn key(key: &[u8]) -> SKINNYeeKey {
+        assert_eq!(key.len(), 16);
+        let sub_keys = to_nibbles(key)
+            .chunks(8)
+            .map(|sub_key| Matrix::new(2, 4, sub_key.to_vec()))
+            .collect();
+        SKINNYeeKey::new(sub_keys, RC_INIT)
+    }
+
+    fn tweak(domain: u8, counter: u64, data: &[u8; TWEAK_DATA_LEN]) -> Matrix<u8> {
+        assert!(counter < 1 << 60);
+        let mut tweak = to_nibbles(&counter.to_be_bytes());
+        tweak[0] = domain;
+        tweak.extend(to_nibbles(data));
+        Matrix::new(16, 4, tweak)
+    }
+
+    fn encrypt_state(&self, key: &SKINNYeeKey, tweak: &Matrix<u8>, state: &mut [u8; BLOCK_LEN]) {
+        let mut block = Matrix::new(4, 4, to_nibbles(state));
+        self.skinny.encrypt(key, tweak, &mut block);
+        state.copy_from_slice(&from_nibbles(&block.values));
+    }
+
+    fn check_lengths(key: &[u8], nonce: &[u8]) {
+        assert_eq!(key.len(), 16);
+        assert_eq!(nonce.len(), NONCE_LEN);
+    }
+
+    fn tag(&self, key: &SKINNYeeKey, nonce: &[u8], ad: &[u8], message: &[u8]) -> [u8; BLOCK_LEN] {
+        let mut state = [0; BLOCK_LEN];
+        let header = [nonce, ad].concat();
+        let chunks = header.chunks(TWEAK_DATA_LEN).collect::<Vec<_>>();
+        for (i, chunk) in chunks.iter().enumerate() {
+            let domain = match (i + 1 == chunks.len(), chunk.len() < TWEAK_DATA_LEN) {
+                (false, _) => HEADER,
+                (true, false) => HEADER_FINAL,
+                (true, true) => HEADER_FINAL_PADDED,
+            };
+            self.encrypt_state(key, &Self::tweak(domain, i as u64 + 1, &pad_10(chunk)), &mut state);
+        }
+        for (i, block) in message.chunks(BLOCK_LEN).enumerate() {
+            let domain = if block.len() < BLOCK_LEN { MESSAGE_FINAL_PADDED } else { MESSAGE };
+            state.iter_mut().zip(pad_10::<BLOCK_LEN>(block)).for_each(|(s, m)| *s ^= m);
+            self.encrypt_state(key, &Self::tweak(domain, i as u64 + 1, &[0; TWEAK_DATA_LEN]), &mut state);
+        }
+        self.encrypt_state(key, &Self::tweak(TAG, 0, &[0; TWEAK_DATA_LEN]), &mut state);
+        state
+    }
+
+    /// Counter mode with the tag in the tweak, its own inverse.
+    fn process_message(&self, key: &SKINNYeeKey, tag: &[u8; BLOCK_LEN], input: &[u8]) -> Vec<u8> {
+        let mut tag_data = [0; TWEAK_DATA_LEN];
+        tag_data[..BLOCK_LEN].copy_from_slice(tag);
+        input.chunks(BLOCK_LEN)
+            .enumerate()
+            .flat_map(|(i, block)| {
+                let mut key_stream = *tag;
+                self.encrypt_state(key, &Self::tweak(ENCRYPTION, i as u64 + 1, &tag_data), &mut key_stream);
+                block.iter().zip(key_stream).map(|(x, z)| x ^ z).collect::<Vec<_>>()
+            })
+            .collect()
+    }
+}
+
+impl Aead for LmDae {
+    fn encrypt(&self, key: &[u8], nonce: &[u8], ad: &[u8], message: &[u8]) -> (Vec<u8>, Vec<u8>) {
+        Self::check_lengths(key, nonce);
+        let key = Self::key(key);
+        let tag = self.tag(&key, nonce, ad, message);
+        (self.process_message(&key, &tag, message), tag.to_vec())
+    }
+
+    fn decrypt(&self, key: &[u8], nonce: &[u8], ad: &[u8], ciphertext: &[u8], tag: &[u8]) -> Result<Vec<u8>, AuthenticationError> {
+        Self::check_lengths(key, nonce);
+        let key = Self::key(key);
+        let iv: [u8; BLOCK_LEN] = tag.try_into().map_err(|_| AuthenticationError)?;
+        let message = self.process_message(&key, &iv, ciphertext);
+        verify_tag(&self.tag(&key, nonce, ad, &message), tag)?;
+        Ok(message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::aead::lm_dae::{LmDae, RC_INIT};
+    use crate::aead::Aead;
+    use crate::aead::tests::check_round_trip;
+
+    #[test]
+    fn test_round_trip_lm_dae() {
+        check_round_trip(&LmDae::default(), 8, 8, 8);
+    }
+
+    #[test]
+    fn test_key() {
+        let key = LmDae::key(&[0xA5; 16]);
+        assert_eq!(key.rc_init, RC_INIT);
+        assert!(key.sub_keys.iter().all(|sub_key| sub_key.values == [0xA, 0x5, 0xA, 0x5, 0xA, 0x5, 0xA, 0x5]));
+    }
+
+    #[test]
+    fn test_deterministic_lm_dae() {
+        let aead = LmDae::default();
+        let key = [0x5A; 16];
+        let nonce = [0; 8];
+        assert_eq!(aead.encrypt(&key, &nonce, b"header", b"message"), aead.encrypt(&key, &nonce, b"header", b"message"));
+        assert_ne!(aead.encrypt(&key, &nonce, b"header", b"message").1, aead.encrypt(&key, &nonce, b"header!", b"message").1);
+    }
+}
@@ -0,0 +1,108 @@
+pub mod lm_dae;
+pub mod pfb;
+pub mod romulus;
+pub mod skinny_aead;
+
+/// The tag does not match the ciphertext, nonce and associated data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AuthenticationError;
+
+/// Nonce-based authenticated encryption with associated data, on byte strings.
+pub trait Aead {
+    /// Returns `(ciphertext, tag)`.
+    fn encrypt(&self, key: &[u8], nonce: &[u8], ad: &[u8], message: &[u8]) -> (Vec<u8>, Vec<u8>);
+    fn decrypt(&self, key: &[u8], nonce: &[u8], ad: &[u8], ciphertext: &[u8], tag: &[u8]) -> Result<Vec<u8>, AuthenticationError>;
+}
+
+/// Splits `x` in 16-byte blocks, the last one possibly partial. The empty string is one empty block.
+pub(crate) fn blocks(x: &[u8]) -> Vec<&[u8]> {
+    if x.is_empty() {
+        vec![x]
+    } else {
+        x.chunks(16).collect()
+    }
+}
+
+/// `x || 0* || |x|` for a partial block, `x` for a full one.
+pub(crate) fn pad(x: &[u8]) -> [u8; 16] {
+    assert!(x.len() <= 16);
+    let mut block = [0; 16];
+    block[..x.len()].copy_from_slice(x);
+    if x.len() < 16 {
+        block[15] = x.len() as u8;
+    }
+    block
+}
+
+/// `x || 1 || 0*` for a partial block, `x` for a full one.
+pub(crate) fn pad_10<const N: usize>(x: &[u8]) -> [u8; N] {
+    assert!(x.len() <= N);
+    let mut block = [0; N];
+    block[..x.len()].copy_from_slice(x);
+    if x.len() < N {
+        block[x.len()] = 0x80;
+    }
+    block
+}
+
+/// Splits each byte in two nibbles, most significant first, for the 64-bit block ciphers.
+pub(crate) fn to_nibbles(bytes: &[u8]) -> Vec<u8> {
+    bytes.iter().flat_map(|b| [b >> 4, b & 0xF]).collect()
+}
+
+pub(crate) fn from_nibbles(nibbles: &[u8]) -> Vec<u8> {
+    nibbles.chunks(2).map(|n| n[0] << 4 | n[1]).collect()
+}
+
+/// Tag comparison whose running time does not depend on the position of the first difference.
+pub(crate) fn verify_tag(expected: &[u8], tag: &[u8]) -> Result<(), AuthenticationError> {
+    let difference = expected.iter()
+        .zip(tag)
+        .fold((expected.len() != tag.len()) as u8, |acc, (a, b)| acc | (a ^ b));
+    if difference == 0 {
+        Ok(())
+    } else {
+        Err(AuthenticationError)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use crate::aead::{Aead, AuthenticationError};
+
+    /// Round trip over every pair of AD and message lengths around one and two blocks of
+    /// `block_len` bytes, then a flipped bit in the tag, the ciphertext, the AD or the nonce must be
+    /// rejected. Modes keep their own reference vectors, this only covers the plumbing.
+    pub(crate) fn check_round_trip(aead: &impl Aead, nonce_len: usize, tag_len: usize, block_len: usize) {
+        let key = (0..16).collect::<Vec<u8>>();
+        let nonce = (16..16 + nonce_len as u8).collect::<Vec<u8>>();
+        let lengths = [0, 1, block_len - 1, block_len, block_len + 1, 2 * block_len, 3 * block_len + 2];
+        let data = (0..3 * block_len + 2).map(|i| (i * 7 + 3) as u8).collect::<Vec<_>>();
+        for ad_len in lengths {
+            for message_len in lengths {
+                let (ad, message) = (&data[..ad_len], &data[data.len() - message_len..]);
+                let (ciphertext, tag) = aead.encrypt(&key, &nonce, ad, message);
+                assert_eq!(ciphertext.len(), message_len);
+                assert_eq!(tag.len(), tag_len);
+                assert_eq!(aead.decrypt(&key, &nonce, ad, &ciphertext, &tag), Ok(message.to_vec()));
+
+                let mut forged_tag = tag.clone();
+                forged_tag[tag_len - 1] ^= 1;
+                assert_eq!(aead.decrypt(&key, &nonce, ad, &ciphertext, &forged_tag), Err(AuthenticationError));
+                if message_len > 0 {
+                    let mut forged_ciphertext = ciphertext.clone();
+                    forged_ciphertext[message_len - 1] ^= 0x80;
+                    assert_eq!(aead.decrypt(&key, &nonce, ad, &forged_ciphertext, &tag), Err(AuthenticationError));
+                }
+                if ad_len > 0 {
+                    let mut forged_ad = ad.to_vec();
+                    forged_ad[0] ^= 1;
+                    assert_eq!(aead.decrypt(&key, &nonce, &forged_ad, &ciphertext, &tag), Err(AuthenticationError));
+                }
+                let mut forged_nonce = nonce.clone();
+                forged_nonce[0] ^= 1;
+                assert_eq!(aead.decrypt(&key, &forged_nonce, ad, &ciphertext, &tag), Err(AuthenticationError));
+            }
+        }
+    }
+}
@@ -0,0 +1,111 @@
+use crate::aead::{from_nibbles, pad_10, to_nibbles, verify_tag, Aead, AuthenticationError};
+use crate::ciphers::skinnye_v2::SKINNYe_v2;
+use crate::ciphers::TweakableBlockCipher;
+use crate::matrix::Matrix;
+
+// Domain separation nibble, first cell of the tweak.
+const AD: u8 = 0x0;
+const AD_FINAL_PADDED: u8 = 0x1;
+const MESSAGE: u8 = 0x2;
+const TAG: u8 = 0x4;
+const TAG_PADDED: u8 = 0x5;
+
+const BLOCK_LEN: usize = 8;
+
+/// PFB (plaintext feedback) on SKINNYe-v2 with a 256-bit tweakey: TK1 holds the domain nibble
+/// and a 60-bit block counter, TK2 the 64-bit nonce, TK3 and TK4 the 128-bit key. Each key
+/// stream block is the encryption of the previous plaintext block, the tag the encryption of
+/// the last one. Blocks and tags are 64-bit.
+#[derive(Default)]
+pub struct Pfb {
+    skinny: SKINNYe_v2,
+}
+
+impl Pfb {
+    fn tweak(nonce: &[u8], counter: u64, domain: u8) -> Matrix<u8> {
+        assert!(counter < 1 << 60);
+        let mut tweak = to_nibbles(&counter.to_be_bytes());
+        tweak[0] = domain;
+        tweak.extend(to_nibbles(nonce));
+        Matrix::new(2, 16, tweak)
+    }
+
+    fn encrypt_state(&self, key: &Matrix<u8>, nonce: &[u8], counter: u64, domain: u8, state: &mut [u8; BLOCK_LEN]) {
+        let mut block = Matrix::new(4, 4, to_nibbles(state));
+        self.skinny.encrypt(key, &Self::tweak(nonce, counter, domain), &mut block);
+        state.copy_from_slice(&from_nibbles(&block.values));
+    }
+
+    fn check_lengths(key: &[u8], nonce: &[u8]) {
+        assert_eq!(key.len(), 16);
+        assert_eq!(nonce.len(), BLOCK_LEN);
+    }
+
+    /// CBC-MAC of the associated data, each block encrypted with its own counter.
+    fn process_ad(&self, key: &Matrix<u8>, nonce: &[u8], ad: &[u8]) -> [u8; BLOCK_LEN] {
+        let mut state = [0; BLOCK_LEN];
+        for (i, block) in ad.chunks(BLOCK_LEN).enumerate() {
+            let domain = if block.len() < BLOCK_LEN { AD_FINAL_PADDED } else { AD };
+            state.iter_mut().zip(pad_10::<BLOCK_LEN>(block)).for_each(|(s, a)| *s ^= a);
+            self.encrypt_state(key, nonce, i as u64 + 1, domain, &mut state);
+        }
+        state
+    }
+
+    /// Encrypts or decrypts `input` from the state left by the associated data, and returns the
+    /// output with the tag.
+    fn process_message(&self, key: &Matrix<u8>, nonce: &[u8], mut state: [u8; BLOCK_LEN], input: &[u8], decrypt: bool) -> (Vec<u8>, Vec<u8>) {
+        let mut output = Vec::with_capacity(input.len());
+        let mut counter = 1;
+        let mut tag_domain = TAG;
+        for block in input.chunks(BLOCK_LEN) {
+            self.encrypt_state(key, nonce, counter, MESSAGE, &mut state);
+            let block_output = block.iter().zip(state).map(|(x, z)| x ^ z).collect::<Vec<_>>();
+            let message = if decrypt { &block_output[..] } else { block };
+            state = pad_10(message);
+            if block.len() < BLOCK_LEN {
+                tag_domain = TAG_PADDED;
+            }
+            output.extend(block_output);
+            counter += 1;
+        }
+        self.encrypt_state(key, nonce, counter, tag_domain, &mut state);
+        (output, state.to_vec())
+    }
+}
+
+impl Aead for Pfb {
+    fn encrypt(&self, key: &[u8], nonce: &[u8], ad: &[u8], message: &[u8]) -> (Vec<u8>, Vec<u8>) {
+        Self::check_lengths(key, nonce);
+        let key = Matrix::new(2, 16, to_nibbles(key));
+        let state = self.process_ad(&key, nonce, ad);
+        self.process_message(&key, nonce, state, message, false)
+    }
+
+    fn decrypt(&self, key: &[u8], nonce: &[u8], ad: &[u8], ciphertext: &[u8], tag: &[u8]) -> Result<Vec<u8>, AuthenticationError> {
+        Self::check_lengths(key, nonce);
+        let key = Matrix::new(2, 16, to_nibbles(key));
+        let state = self.process_ad(&key, nonce, ad);
+        let (message, expected_tag) = self.process_message(&key, nonce, state, ciphertext, true);
+        verify_tag(&expected_tag, tag)?;
+        Ok(message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::aead::pfb::Pfb;
+    use crate::aead::tests::check_round_trip;
+
+    #[test]
+    fn test_round_trip_pfb() {
+        check_round_trip(&Pfb::default(), 8, 8, 8);
+    }
+
+    #[test]
+    fn test_tweak_layout() {
+        let tweak = Pfb::tweak(&[0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef], 0x12, 0x5);
+        assert_eq!(tweak.values[..16], [5, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 2]);
+        assert_eq!(tweak.values[16..], [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 0xa, 0xb, 0xc, 0xd, 0xe, 0xf]);
+    }
+}
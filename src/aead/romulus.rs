@@ -0,0 +1,253 @@
+use crate::aead::{blocks, pad, verify_tag, Aead, AuthenticationError};
+use crate::ciphers::skinny::SKINNY;
+use crate::ciphers::SymmetricCipher;
+use crate::matrix::Matrix;
+
+// Domain separation bytes, stored in byte 7 of TK1.
+const AD: u8 = 0x08;
+const AD_FINAL: u8 = 0x18;
+const AD_FINAL_PADDED: u8 = 0x1A;
+const MESSAGE: u8 = 0x04;
+const MESSAGE_FINAL: u8 = 0x14;
+const MESSAGE_FINAL_PADDED: u8 = 0x15;
+const M_AD: u8 = 0x28;
+const M_MESSAGE: u8 = 0x2C;
+const M_FINAL: u8 = 0x30;
+const M_ENCRYPTION: u8 = 0x24;
+
+/// 56-bit block counter of TK1, multiplied by x in GF(2^56) (x^56 + x^7 + x^4 + x^2 + 1) at each step.
+#[derive(Clone)]
+struct Counter([u8; 7]);
+
+impl Counter {
+    fn new() -> Counter {
+        Counter([1, 0, 0, 0, 0, 0, 0])
+    }
+
+    fn step(&mut self) {
+        let feedback = self.0[6] >> 7;
+        for i in (1..7).rev() {
+            self.0[i] = self.0[i] << 1 | self.0[i - 1] >> 7;
+        }
+        self.0[0] = self.0[0] << 1 ^ if feedback == 1 { 0x95 } else { 0 };
+    }
+}
+
+/// Romulus v1.3 on SKINNY-128-384+ (40 rounds): TK1 holds the counter and the domain, TK2 the
+/// nonce or an associated data block, TK3 the key.
+struct Romulus {
+    skinny: SKINNY,
+}
+
+impl Romulus {
+    fn new() -> Romulus {
+        Romulus { skinny: SKINNY::v128_with_rounds(40) }
+    }
+
+    fn encrypt_state(&self, state: &mut [u8; 16], counter: &Counter, domain: u8, tweak: &[u8], key: &[u8]) {
+        let mut tweakey = Vec::with_capacity(48);
+        tweakey.extend(counter.0);
+        tweakey.push(domain);
+        tweakey.extend([0; 8]);
+        tweakey.extend(tweak);
+        tweakey.extend(key);
+        let mut block = Matrix::new(4, 4, state.to_vec());
+        self.skinny.cipher(&Matrix::new(3, 16, tweakey), &mut block);
+        state.copy_from_slice(&block.values);
+    }
+
+    /// Output of the feedback function G on the state.
+    fn g(state: &[u8; 16]) -> [u8; 16] {
+        state.map(|s| (s >> 1) ^ (s & 0x80) ^ ((s & 0x01) << 7))
+    }
+
+    /// rho: returns `M ^ G(S)` truncated to `|M|` and absorbs `pad(M)` in the state.
+    fn rho(state: &mut [u8; 16], message: &[u8]) -> Vec<u8> {
+        let g = Self::g(state);
+        let padded = pad(message);
+        state.iter_mut().zip(padded).for_each(|(s, m)| *s ^= m);
+        message.iter().zip(g).map(|(m, g)| m ^ g).collect()
+    }
+
+    /// Inverse of rho: returns `C ^ G(S)` truncated to `|C|` and absorbs the padded message.
+    fn inv_rho(state: &mut [u8; 16], ciphertext: &[u8]) -> Vec<u8> {
+        let message = ciphertext.iter().zip(Self::g(state)).map(|(c, g)| c ^ g).collect::<Vec<_>>();
+        let padded = pad(&message);
+        state.iter_mut().zip(padded).for_each(|(s, m)| *s ^= m);
+        message
+    }
+
+    fn check_lengths(key: &[u8], nonce: &[u8]) {
+        assert_eq!(key.len(), 16);
+        assert_eq!(nonce.len(), 16);
+    }
+
+    /// Absorbs the blocks `x` two by two, the first one in the state and the second one in TK2,
+    /// then finishes with the nonce in TK2. Returns the state after the final call.
+    fn absorb(&self, key: &[u8], nonce: &[u8], x: &[[u8; 16]], domain: impl Fn(usize) -> u8, final_domain: u8) -> [u8; 16] {
+        let mut state = [0; 16];
+        let mut counter = Counter::new();
+        for i in 0..x.len() / 2 {
+            Self::rho(&mut state, &x[2 * i]);
+            counter.step();
+            self.encrypt_state(&mut state, &counter, domain(i), &x[2 * i + 1], key);
+            counter.step();
+        }
+        if x.len() % 2 == 1 {
+            Self::rho(&mut state, &x[x.len() - 1]);
+            counter.step();
+        }
+        self.encrypt_state(&mut state, &counter, final_domain, nonce, key);
+        state
+    }
+}
+
+/// Nonce-based AEAD Romulus-N, with a 128-bit key, nonce and tag.
+pub struct RomulusN {
+    romulus: Romulus,
+}
+
+impl Default for RomulusN {
+    fn default() -> RomulusN {
+        RomulusN { romulus: Romulus::new() }
+    }
+}
+
+impl RomulusN {
+    fn process_ad(&self, key: &[u8], nonce: &[u8], ad: &[u8]) -> [u8; 16] {
+        let ad_blocks = blocks(ad);
+        let final_domain = if ad_blocks.last().unwrap().len() < 16 { AD_FINAL_PADDED } else { AD_FINAL };
+        let ad_blocks = ad_blocks.into_iter().map(pad).collect::<Vec<_>>();
+        self.romulus.absorb(key, nonce, &ad_blocks, |_| AD, final_domain)
+    }
+
+    /// Runs the message phase, `rho` being either rho (encryption) or its inverse (decryption).
+    fn process_message(&self, key: &[u8], nonce: &[u8], mut state: [u8; 16], input: &[u8], rho: fn(&mut [u8; 16], &[u8]) -> Vec<u8>) -> (Vec<u8>, Vec<u8>) {
+        let input_blocks = blocks(input);
+        let m = input_blocks.len();
+        let mut counter = Counter::new();
+        let mut output = Vec::with_capacity(input.len());
+        for (i, block) in input_blocks.iter().enumerate() {
+            output.extend(rho(&mut state, block));
+            counter.step();
+            let domain = match (i + 1 == m, block.len() < 16) {
+                (false, _) => MESSAGE,
+                (true, false) => MESSAGE_FINAL,
+                (true, true) => MESSAGE_FINAL_PADDED,
+            };
+            self.romulus.encrypt_state(&mut state, &counter, domain, nonce, key);
+        }
+        (output, Romulus::g(&state).to_vec())
+    }
+}
+
+impl Aead for RomulusN {
+    fn encrypt(&self, key: &[u8], nonce: &[u8], ad: &[u8], message: &[u8]) -> (Vec<u8>, Vec<u8>) {
+        Romulus::check_lengths(key, nonce);
+        let state = self.process_ad(key, nonce, ad);
+        self.process_message(key, nonce, state, message, Romulus::rho)
+    }
+
+    fn decrypt(&self, key: &[u8], nonce: &[u8], ad: &[u8], ciphertext: &[u8], tag: &[u8]) -> Result<Vec<u8>, AuthenticationError> {
+        Romulus::check_lengths(key, nonce);
+        let state = self.process_ad(key, nonce, ad);
+        let (message, expected_tag) = self.process_message(key, nonce, state, ciphertext, Romulus::inv_rho);
+        verify_tag(&expected_tag, tag)?;
+        Ok(message)
+    }
+}
+
+/// Misuse-resistant AEAD Romulus-M (SIV-like: MAC over the associated data and the message,
+/// then encryption with the tag as IV), with a 128-bit key, nonce and tag.
+pub struct RomulusM {
+    romulus: Romulus,
+}
+
+impl Default for RomulusM {
+    fn default() -> RomulusM {
+        RomulusM { romulus: Romulus::new() }
+    }
+}
+
+impl RomulusM {
+    fn tag(&self, key: &[u8], nonce: &[u8], ad: &[u8], message: &[u8]) -> [u8; 16] {
+        let ad_blocks = blocks(ad);
+        let message_blocks = blocks(message);
+        let (a, m) = (ad_blocks.len(), message_blocks.len());
+        let mut w = M_FINAL;
+        if ad_blocks[a - 1].len() < 16 {
+            w ^= 2;
+        }
+        if message_blocks[m - 1].len() < 16 {
+            w ^= 1;
+        }
+        if a % 2 == 0 {
+            w ^= 8;
+        }
+        if m % 2 == 0 {
+            w ^= 4;
+        }
+        let x = ad_blocks.into_iter()
+            .chain(message_blocks)
+            .map(pad)
+            .collect::<Vec<_>>();
+        let state = self.romulus.absorb(key, nonce, &x, |i| if i < a / 2 { M_AD } else { M_MESSAGE }, w);
+        Romulus::g(&state)
+    }
+
+    /// Encryption phase, `rho` being either rho (encryption) or its inverse (decryption).
+    fn process_message(&self, key: &[u8], nonce: &[u8], tag: &[u8; 16], input: &[u8], rho: fn(&mut [u8; 16], &[u8]) -> Vec<u8>) -> Vec<u8> {
+        let mut state = *tag;
+        let mut counter = Counter::new();
+        let mut output = Vec::with_capacity(input.len());
+        for block in input.chunks(16) {
+            self.romulus.encrypt_state(&mut state, &counter, M_ENCRYPTION, nonce, key);
+            output.extend(rho(&mut state, block));
+            counter.step();
+        }
+        output
+    }
+}
+
+impl Aead for RomulusM {
+    fn encrypt(&self, key: &[u8], nonce: &[u8], ad: &[u8], message: &[u8]) -> (Vec<u8>, Vec<u8>) {
+        Romulus::check_lengths(key, nonce);
+        let tag = self.tag(key, nonce, ad, message);
+        (self.process_message(key, nonce, &tag, message, Romulus::rho), tag.to_vec())
+    }
+
+    fn decrypt(&self, key: &[u8], nonce: &[u8], ad: &[u8], ciphertext: &[u8], tag: &[u8]) -> Result<Vec<u8>, AuthenticationError> {
+        Romulus::check_lengths(key, nonce);
+        let iv: [u8; 16] = tag.try_into().map_err(|_| AuthenticationError)?;
+        let message = self.process_message(key, nonce, &iv, ciphertext, Romulus::inv_rho);
+        verify_tag(&self.tag(key, nonce, ad, &message), tag)?;
+        Ok(message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::aead::romulus::{Counter, RomulusM, RomulusN};
+    use crate::aead::tests::check_round_trip;
+
+    #[test]
+    fn test_round_trip_romulus_n() {
+        check_round_trip(&RomulusN::default(), 16, 16, 16);
+    }
+
+    #[test]
+    fn test_round_trip_romulus_m() {
+        check_round_trip(&RomulusM::default(), 16, 16, 16);
+    }
+
+    #[test]
+    fn test_counter() {
+        let mut counter = Counter::new();
+        for _ in 0..55 {
+            counter.step();
+        }
+        assert_eq!(counter.0, [0, 0, 0, 0, 0, 0, 0x80]);
+        counter.step();
+        assert_eq!(counter.0, [0x95, 0, 0, 0, 0, 0, 0]);
+    }
+}
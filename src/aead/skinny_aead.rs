@@ -0,0 +1,209 @@
+use crate::aead::{verify_tag, Aead, AuthenticationError};
+use crate::ciphers::skinny::SKINNY;
+use crate::ciphers::SymmetricCipher;
+use crate::matrix::Matrix;
+
+// Low bits of the domain separation byte, the high bits identify the family member.
+const MESSAGE: u8 = 0;
+const MESSAGE_FINAL_PADDED: u8 = 1;
+const AD: u8 = 2;
+const AD_FINAL_PADDED: u8 = 3;
+const TAG: u8 = 4;
+const TAG_PADDED: u8 = 5;
+
+/// SKINNY-AEAD M1 to M6: a ΘCB3-like mode where TK1 holds an LFSR block counter and the domain
+/// separation byte, next to the nonce and the key.
+pub struct SkinnyAead {
+    skinny: SKINNY,
+    tk: usize,
+    prefix: u8,
+    nonce_len: usize,
+    tag_len: usize,
+}
+
+impl SkinnyAead {
+    /// SKINNY-128-384, 128-bit nonce, 128-bit tag.
+    pub fn m1() -> SkinnyAead {
+        Self::new(3, 0x00, 16, 16)
+    }
+
+    /// SKINNY-128-384, 96-bit nonce, 128-bit tag.
+    pub fn m2() -> SkinnyAead {
+        Self::new(3, 0x10, 12, 16)
+    }
+
+    /// SKINNY-128-384, 128-bit nonce, 64-bit tag.
+    pub fn m3() -> SkinnyAead {
+        Self::new(3, 0x08, 16, 8)
+    }
+
+    /// SKINNY-128-384, 96-bit nonce, 64-bit tag.
+    pub fn m4() -> SkinnyAead {
+        Self::new(3, 0x18, 12, 8)
+    }
+
+    /// SKINNY-128-256, 96-bit nonce, 128-bit tag.
+    pub fn m5() -> SkinnyAead {
+        Self::new(2, 0x10, 12, 16)
+    }
+
+    /// SKINNY-128-256, 96-bit nonce, 64-bit tag.
+    pub fn m6() -> SkinnyAead {
+        Self::new(2, 0x18, 12, 8)
+    }
+
+    fn new(tk: usize, prefix: u8, nonce_len: usize, tag_len: usize) -> SkinnyAead {
+        SkinnyAead { skinny: SKINNY::v128(), tk, prefix, nonce_len, tag_len }
+    }
+
+    /// Block counter, an LFSR over 64 bits (SKINNY-128-384) or 24 bits (SKINNY-128-256).
+    fn step(&self, counter: u64) -> u64 {
+        let width = if self.tk == 3 { 64 } else { 24 };
+        let feedback = if counter >> (width - 1) & 1 == 1 { 0x1B } else { 0 };
+        (counter << 1 ^ feedback) & (u64::MAX >> (64 - width))
+    }
+
+    fn tweakey(&self, key: &[u8], nonce: &[u8], counter: u64, domain: u8) -> Matrix<u8> {
+        let mut tweakey = vec![0; 16 * self.tk];
+        if self.tk == 3 {
+            tweakey[..8].copy_from_slice(&counter.to_le_bytes());
+            tweakey[15] = self.prefix | domain;
+            tweakey[16..16 + nonce.len()].copy_from_slice(nonce);
+            tweakey[32..].copy_from_slice(key);
+        } else {
+            tweakey[..3].copy_from_slice(&counter.to_le_bytes()[..3]);
+            tweakey[3] = self.prefix | domain;
+            tweakey[4..4 + nonce.len()].copy_from_slice(nonce);
+            tweakey[16..].copy_from_slice(key);
+        }
+        Matrix::new(self.tk, 16, tweakey)
+    }
+
+    fn encrypt_block(&self, tweakey: &Matrix<u8>, block: &[u8]) -> Vec<u8> {
+        let mut state = Matrix::new(4, 4, block.to_vec());
+        self.skinny.cipher(tweakey, &mut state);
+        state.values
+    }
+
+    fn check_lengths(&self, key: &[u8], nonce: &[u8]) {
+        assert_eq!(key.len(), 16);
+        assert_eq!(nonce.len(), self.nonce_len);
+    }
+
+    /// Encrypts or decrypts `input` and returns the output with the full 128-bit tag before the
+    /// associated data is taken into account.
+    fn process_message(&self, key: &[u8], nonce: &[u8], input: &[u8], decrypt: bool) -> (Vec<u8>, Vec<u8>) {
+        let mut checksum = [0; 16];
+        let mut output = Vec::with_capacity(input.len());
+        let mut counter = 1;
+        let mut blocks = input.chunks_exact(16);
+        for block in &mut blocks {
+            let tweakey = self.tweakey(key, nonce, counter, MESSAGE);
+            let mut state = Matrix::new(4, 4, block.to_vec());
+            if decrypt {
+                self.skinny.decipher(&tweakey, &mut state);
+            } else {
+                self.skinny.cipher(&tweakey, &mut state);
+            }
+            let message = if decrypt { &state.values[..] } else { block };
+            checksum.iter_mut().zip(message).for_each(|(c, m)| *c ^= m);
+            output.extend(&state.values);
+            counter = self.step(counter);
+        }
+        let last = blocks.remainder();
+        let tag_domain = if last.is_empty() {
+            TAG
+        } else {
+            let pad = self.encrypt_block(&self.tweakey(key, nonce, counter, MESSAGE_FINAL_PADDED), &[0; 16]);
+            let last_output = last.iter().zip(&pad).map(|(x, p)| x ^ p).collect::<Vec<_>>();
+            let message = if decrypt { &last_output[..] } else { last };
+            checksum.iter_mut().zip(message).for_each(|(c, m)| *c ^= m);
+            checksum[last.len()] ^= 0x80;
+            output.extend(last_output);
+            counter = self.step(counter);
+            TAG_PADDED
+        };
+        let tag = self.encrypt_block(&self.tweakey(key, nonce, counter, tag_domain), &checksum);
+        (output, tag)
+    }
+
+    /// XORs the ΘCB3 hash of `ad` to `tag`. The hash does not depend on the nonce, its field in the
+    /// tweakey is left to zero.
+    fn authenticate(&self, key: &[u8], ad: &[u8], tag: &mut [u8]) {
+        let zero_nonce = vec![0; self.nonce_len];
+        let mut counter = 1;
+        for block in ad.chunks(16) {
+            let (domain, block) = if block.len() == 16 {
+                (AD, block.to_vec())
+            } else {
+                let mut padded = block.to_vec();
+                padded.push(0x80);
+                padded.resize(16, 0);
+                (AD_FINAL_PADDED, padded)
+            };
+            let output = self.encrypt_block(&self.tweakey(key, &zero_nonce, counter, domain), &block);
+            tag.iter_mut().zip(output).for_each(|(t, o)| *t ^= o);
+            counter = self.step(counter);
+        }
+    }
+
+    fn tag(&self, key: &[u8], ad: &[u8], mut tag: Vec<u8>) -> Vec<u8> {
+        self.authenticate(key, ad, &mut tag);
+        tag.truncate(self.tag_len);
+        tag
+    }
+}
+
+impl Aead for SkinnyAead {
+    fn encrypt(&self, key: &[u8], nonce: &[u8], ad: &[u8], message: &[u8]) -> (Vec<u8>, Vec<u8>) {
+        self.check_lengths(key, nonce);
+        let (ciphertext, tag) = self.process_message(key, nonce, message, false);
+        (ciphertext, self.tag(key, ad, tag))
+    }
+
+    fn decrypt(&self, key: &[u8], nonce: &[u8], ad: &[u8], ciphertext: &[u8], tag: &[u8]) -> Result<Vec<u8>, AuthenticationError> {
+        self.check_lengths(key, nonce);
+        let (message, expected_tag) = self.process_message(key, nonce, ciphertext, true);
+        verify_tag(&self.tag(key, ad, expected_tag), tag)?;
+        Ok(message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::aead::skinny_aead::SkinnyAead;
+    use crate::aead::Aead;
+    use crate::aead::tests::check_round_trip;
+
+    #[test]
+    fn test_round_trip_skinny_aead() {
+        for aead in [SkinnyAead::m1(), SkinnyAead::m2(), SkinnyAead::m3(), SkinnyAead::m4(), SkinnyAead::m5(), SkinnyAead::m6()] {
+            check_round_trip(&aead, aead.nonce_len, aead.tag_len, 16);
+        }
+    }
+
+    #[test]
+    fn test_ad_hash_is_nonce_independent() {
+        for aead in [SkinnyAead::m1(), SkinnyAead::m5()] {
+            let key = (0..16).collect::<Vec<u8>>();
+            let ad = (0..40).collect::<Vec<u8>>();
+            let ad_hash = |nonce: &[u8]| {
+                let (_, with_ad) = aead.encrypt(&key, nonce, &ad, &[]);
+                let (_, without_ad) = aead.encrypt(&key, nonce, &[], &[]);
+                with_ad.iter().zip(without_ad).map(|(a, b)| a ^ b).collect::<Vec<_>>()
+            };
+            let hash = ad_hash(&vec![0; aead.nonce_len]);
+            assert_ne!(hash, vec![0; aead.tag_len]);
+            assert_eq!(ad_hash(&vec![0xA5; aead.nonce_len]), hash);
+        }
+    }
+
+    #[test]
+    fn test_counter() {
+        let aead = SkinnyAead::m5();
+        assert_eq!(aead.step(1 << 22), 1 << 23);
+        assert_eq!(aead.step(1 << 23), 0x1B);
+        let aead = SkinnyAead::m1();
+        assert_eq!(aead.step(1 << 63), 0x1B);
+    }
+}
@@ -0,0 +1,85 @@
+use std::fs::File;
+use std::io;
+use std::io::BufReader;
+use std::path::PathBuf;
+
+use clap::Parser;
+use rand::SeedableRng;
+use rand_xoshiro::Xoshiro256StarStar;
+use rayon::prelude::*;
+
+use ciphers::craft::CRAFT;
+use ciphers::differential_characteristics::rt_craft::RelatedTweakCraftDifferentialCharacteristic;
+use ciphers::differential_characteristics::validation::to_io_error;
+use ciphers::matrix::Matrix;
+use ciphers::skinny_common::random_tweakey;
+use ciphers::skinny_plaintext_generator::SkinnyPlaintextGenerator;
+use ciphers::evaluators::evaluate_differential_characteristic;
+
+#[derive(Parser)]
+struct Args {
+    #[arg(short('k'), long, default_value_t = 8)]
+    nb_key: usize,
+    #[arg(short, long)]
+    nb_tries_per_key: Option<usize>,
+    #[arg(short, long)]
+    path: PathBuf,
+}
+
+fn main() -> io::Result<()> {
+    let args: Args = Args::parse();
+    let seed = [
+        6, 3, 14, 7, 11, 12, 0, 15, 8, 2, 1, 10, 4, 5, 9, 13,
+        14, 9, 6, 3, 7, 2, 8, 11, 10, 5, 0, 15, 1, 4, 13, 12
+    ];
+
+    let mut rand = Xoshiro256StarStar::from_seed(seed);
+
+    let path = File::open(&args.path).unwrap();
+    let reader = BufReader::new(path);
+    let dc: RelatedTweakCraftDifferentialCharacteristic = serde_json::de::from_reader(reader)?;
+    dc.validate().map_err(to_io_error)?;
+
+    let (cipher, mask) = (CRAFT::full_rounds(dc.x.len() - 1), 0xF);
+
+    let input_difference = dc.x.first().unwrap().iter().flatten().cloned().collect::<Vec<_>>();
+    let input_difference = Matrix::new(4, 4, input_difference);
+
+    let output_difference = dc.x.last().unwrap().iter().flatten().cloned().collect::<Vec<_>>();
+    let output_difference = Matrix::new(4, 4, output_difference);
+
+    // Tweakey T || K0 || K1, only the tweak lane carries a difference.
+    let mut tweakey_difference = dc.t.clone();
+    tweakey_difference.resize(48, 0);
+    let tweakey_difference = Matrix::new(3, 16, tweakey_difference);
+
+    let mut number_of_valid_pairs: usize = 0;
+    let nb_tries_per_key = args.nb_tries_per_key.unwrap_or(1 << (dc.objective + 2));
+
+    for key_no in 0..args.nb_key {
+        let key = random_tweakey(&mut rand, 3, mask);
+        let key_xor_tweakey_difference = &key ^ &tweakey_difference;
+
+        let key = cipher.expand(&key);
+        let key_xor_tweakey_difference = cipher.expand(&key_xor_tweakey_difference);
+        let generator = SkinnyPlaintextGenerator::new(&mut rand, mask);
+        let number_of_valid_pairs_for_key: usize = generator.take(nb_tries_per_key)
+            .par_bridge()
+            .map(|p0| evaluate_differential_characteristic(
+                &key,
+                p0,
+                &input_difference,
+                &output_difference,
+                &key_xor_tweakey_difference
+            ))
+            .fold(|| 0usize, |a, b| a + b)
+            .sum();
+
+        number_of_valid_pairs += number_of_valid_pairs_for_key;
+        println!("Random Key {} - {}/{} : 2^{{{}}}", key_no, number_of_valid_pairs_for_key, nb_tries_per_key, (number_of_valid_pairs_for_key as f64).log2() - ((nb_tries_per_key) as f64).log2());
+    }
+
+    println!("Total - {}/{} : 2^{{{}}}", number_of_valid_pairs, nb_tries_per_key * args.nb_key, (number_of_valid_pairs as f64).log2() - ((nb_tries_per_key * args.nb_key) as f64).log2());
+    println!("Mean  - {}/{} : 2^{{{}}}", number_of_valid_pairs as f64 / args.nb_key as f64, nb_tries_per_key, (number_of_valid_pairs as f64 / args.nb_key as f64).log2() - ((nb_tries_per_key) as f64).log2());
+    Ok(())
+}
@@ -0,0 +1,131 @@
+use std::fs::File;
+use std::io;
+use std::io::BufReader;
+use std::path::PathBuf;
+
+use clap::Parser;
+use rand::SeedableRng;
+use rand_xoshiro::Xoshiro256StarStar;
+use rayon::prelude::*;
+
+use ciphers::skinny::SKINNY;
+use ciphers::differential_characteristics::rtk_skinny::RelatedTweakeySkinnyDifferentialCharacteristic;
+use ciphers::differential_characteristics::validation::to_io_error;
+use ciphers::matrix::Matrix;
+use ciphers::sbox::Sbox;
+use ciphers::skinny_common::{random_tweakey, tweakey_difference};
+use ciphers::skinny_plaintext_generator::SkinnyPlaintextGenerator;
+use ciphers::evaluators::{TrailStatistics, count_followed_rounds, evaluate_differential_characteristic, trail_states};
+
+#[derive(Copy, Clone, clap::ValueEnum)]
+enum Version {
+    V64,
+    V128,
+}
+
+#[derive(Parser)]
+struct Args {
+    #[arg(short, long)]
+    version: Version,
+    #[arg(short('k'), long, default_value_t = 8)]
+    nb_key: usize,
+    #[arg(short, long)]
+    nb_tries_per_key: Option<usize>,
+    #[arg(short, long)]
+    path: PathBuf,
+    /// Records how many rounds each pair follows the characteristic for, instead of checking the output difference only
+    #[arg(long)]
+    per_round: bool,
+}
+
+fn main() -> io::Result<()> {
+    let args: Args = Args::parse();
+    let seed = [
+        6, 3, 14, 7, 11, 12, 0, 15, 8, 2, 1, 10, 4, 5, 9, 13,
+        14, 9, 6, 3, 7, 2, 8, 11, 10, 5, 0, 15, 1, 4, 13, 12
+    ];
+
+    let mut rand = Xoshiro256StarStar::from_seed(seed);
+
+    let path = File::open(&args.path).unwrap();
+    let reader = BufReader::new(path);
+    let dc: RelatedTweakeySkinnyDifferentialCharacteristic = serde_json::de::from_reader(reader)?;
+    let bits = match args.version {
+        Version::V64 => 4,
+        Version::V128 => 8,
+    };
+    dc.validate(bits).map_err(to_io_error)?;
+
+    let (cipher, mask) = match args.version {
+        Version::V64 => (SKINNY::v64_with_rounds(dc.x.len() - 1), 0xF),
+        Version::V128 => (SKINNY::v128_with_rounds(dc.x.len() - 1), 0xFF),
+    };
+    let sbox = match args.version {
+        Version::V64 => Sbox::skinny_64(),
+        Version::V128 => Sbox::skinny_128(),
+    };
+    let predicted = dc.theoretical_probability(&sbox);
+    if !predicted.matches_objective(dc.objective) {
+        eprintln!("Warning : the DDT weight {} differs from the objective {}", predicted.weight(), dc.objective);
+    }
+
+    let input_difference = dc.x.first().unwrap().iter().flatten().cloned().collect::<Vec<_>>();
+    let input_difference = Matrix::new(4, 4, input_difference);
+
+    let output_difference = dc.x.last().unwrap().iter().flatten().cloned().collect::<Vec<_>>();
+    let output_difference = Matrix::new(4, 4, output_difference);
+
+    let tweakey_difference = tweakey_difference(&dc, 0);
+
+    let (x_states, sc_states) = (trail_states(&dc.x), trail_states(&dc.sc));
+    let rounds = dc.x.len() - 1;
+    let mut statistics = TrailStatistics::new(rounds);
+
+    let mut number_of_valid_pairs: usize = 0;
+    let nb_tries_per_key = args.nb_tries_per_key.unwrap_or(1 << (dc.objective + 2));
+
+    for key_no in 0..args.nb_key {
+        let key = random_tweakey(&mut rand, dc.tk.len(), mask);
+        let key_xor_tweakey_difference = &key ^ &tweakey_difference;
+
+        let key = cipher.expand(&key);
+        let key_xor_tweakey_difference = cipher.expand(&key_xor_tweakey_difference);
+        let generator = SkinnyPlaintextGenerator::new(&mut rand, mask);
+        let number_of_valid_pairs_for_key = if args.per_round {
+            let statistics_for_key = generator.take(nb_tries_per_key)
+                .par_bridge()
+                .map(|p0| count_followed_rounds(&key, p0, &x_states, &sc_states, &key_xor_tweakey_difference))
+                .fold(|| TrailStatistics::new(rounds), |mut statistics, followed_rounds| {
+                    statistics.record(followed_rounds);
+                    statistics
+                })
+                .reduce(|| TrailStatistics::new(rounds), |a, b| a.merge(&b));
+            statistics = statistics.merge(&statistics_for_key);
+            statistics_for_key.reaching(rounds)
+        } else {
+            generator.take(nb_tries_per_key)
+                .par_bridge()
+                .map(|p0| evaluate_differential_characteristic(
+                    &key,
+                    p0,
+                    &input_difference,
+                    &output_difference,
+                    &key_xor_tweakey_difference
+                ))
+                .fold(|| 0usize, |a, b| a + b)
+                .sum()
+        };
+
+        number_of_valid_pairs += number_of_valid_pairs_for_key;
+        println!("Random Key {} - {}/{} : 2^{{{}}}", key_no, number_of_valid_pairs_for_key, nb_tries_per_key, (number_of_valid_pairs_for_key as f64).log2() - ((nb_tries_per_key) as f64).log2());
+    }
+
+    if args.per_round {
+        println!("{}", statistics.report(&predicted));
+    }
+
+    println!("Total - {}/{} : 2^{{{}}}", number_of_valid_pairs, nb_tries_per_key * args.nb_key, (number_of_valid_pairs as f64).log2() - ((nb_tries_per_key * args.nb_key) as f64).log2());
+    println!("Mean  - {}/{} : 2^{{{}}}", number_of_valid_pairs as f64 / args.nb_key as f64, nb_tries_per_key, (number_of_valid_pairs as f64 / args.nb_key as f64).log2() - ((nb_tries_per_key) as f64).log2());
+    println!("Predicted : {}", predicted);
+    Ok(())
+}
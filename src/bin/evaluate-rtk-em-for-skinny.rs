@@ -0,0 +1,89 @@
+use std::fs::File;
+use std::io;
+use std::io::BufReader;
+
+use clap::Parser;
+use indicatif::{ParallelProgressIterator, ProgressBar, ProgressStyle};
+use rand::SeedableRng;
+use rand_xoshiro::Xoshiro256StarStar;
+use rayon::prelude::*;
+
+use ciphers::skinny::SKINNY;
+use ciphers::differential_characteristics::rtk_boom_skinny::RelatedTweakeySkinnyBoomerangCharacteristic;
+use ciphers::differential_characteristics::validation::to_io_error;
+use ciphers::matrix::Matrix;
+use ciphers::skinny_boomerang_cli_args::{Args, Version};
+use ciphers::skinny_common::{random_tweakey, rewind_tweakey_difference, tweakey_difference};
+use ciphers::skinny_plaintext_generator::SkinnyPlaintextGenerator;
+use ciphers::evaluators::evaluate_boomerang;
+
+fn main() -> io::Result<()> {
+    let args: Args = Args::parse();
+    rayon::ThreadPoolBuilder::new().num_threads(args.nb_threads).build_global().unwrap();
+    let seed = [
+        6, 3, 14, 7, 11, 12, 0, 15, 8, 2, 1, 10, 4, 5, 9, 13,
+        14, 9, 6, 3, 7, 2, 8, 11, 10, 5, 0, 15, 1, 4, 13, 12
+    ];
+
+    let mut rand = Xoshiro256StarStar::from_seed(seed);
+
+    let path = File::open(&args.path).unwrap();
+    let reader = BufReader::new(path);
+    let dc: RelatedTweakeySkinnyBoomerangCharacteristic = serde_json::de::from_reader(reader)?;
+    let bits = match args.version {
+        Version::V64 => 4,
+        Version::V128 => 8,
+    };
+    dc.validate(bits).map_err(to_io_error)?;
+
+    let (cipher, mask) = match args.version {
+        Version::V64 => (SKINNY::v64_rounds(dc.r0 - dc.rm..dc.r0), 0xF),
+        Version::V128 => (SKINNY::v128_rounds(dc.r0 - dc.rm..dc.r0), 0xFF),
+    };
+
+    let e0_input_difference = dc.e0_em.x[dc.r0 - dc.rm].iter().flatten().cloned().collect::<Vec<_>>();
+    let e0_input_difference = Matrix::new(4, 4, e0_input_difference);
+
+    let e1_output_difference = dc.em_e1.x[dc.rm].iter().flatten().cloned().collect::<Vec<_>>();
+    let e1_output_difference = Matrix::new(4, 4, e1_output_difference);
+
+    let e0_tks_difference = rewind_tweakey_difference(&cipher, &tweakey_difference(&dc.e0_em, dc.r0 - dc.rm), dc.r0 - dc.rm);
+    let e1_tks_difference = rewind_tweakey_difference(&cipher, &tweakey_difference(&dc.em_e1, 0), dc.r0 - dc.rm);
+
+    let mut number_of_valid_pairs: usize = 0;
+    let nb_tries_per_key = args.nb_tries_per_key.unwrap_or(1 << (dc.e0_em.objective + dc.em_e1.objective + 2));
+
+    for key_no in 0..args.nb_key {
+        let tk = random_tweakey(&mut rand, dc.e0_em.tk.len(), mask);
+        let tk_xor_tke0 = &tk ^ &e0_tks_difference;
+        let tk_xor_tke1 = &tk ^ &e1_tks_difference;
+        let tk_xor_tke0_xor_tke1 = &tk_xor_tke0 ^ &e1_tks_difference;
+        let tk = cipher.expand(&tk);
+        let tk_xor_tke0 = cipher.expand(&tk_xor_tke0);
+        let tk_xor_tke1 = cipher.expand(&tk_xor_tke1);
+        let tk_xor_tke0_xor_tke1 = cipher.expand(&tk_xor_tke0_xor_tke1);
+        let progress_bar = ProgressBar::new(nb_tries_per_key as u64)
+            .with_style(ProgressStyle::with_template("{wide_bar} {pos}/{len} {eta}").unwrap());
+        let generator = SkinnyPlaintextGenerator::new(&mut rand, mask);
+        let number_of_valid_pairs_for_key: usize = generator.take(nb_tries_per_key)
+            .par_bridge()
+            .progress_with(progress_bar)
+            .map(|p0| evaluate_boomerang(
+                &tk, p0,
+                &e0_input_difference,
+                &e1_output_difference,
+                &tk_xor_tke0,
+                &tk_xor_tke1,
+                &tk_xor_tke0_xor_tke1
+            ))
+            .fold(|| 0usize, |a, b| a + b)
+            .sum();
+
+        number_of_valid_pairs += number_of_valid_pairs_for_key;
+        println!("Random Key {} - {}/{} : 2^{{{}}}", key_no, number_of_valid_pairs_for_key, nb_tries_per_key, (number_of_valid_pairs_for_key as f64).log2() - ((nb_tries_per_key) as f64).log2());
+    }
+
+    println!("Total - {}/{} : 2^{{{}}}", number_of_valid_pairs, nb_tries_per_key * args.nb_key, (number_of_valid_pairs as f64).log2() - ((nb_tries_per_key * args.nb_key) as f64).log2());
+    println!("Mean  - {}/{} : 2^{{{}}}", number_of_valid_pairs as f64 / args.nb_key as f64, nb_tries_per_key, (number_of_valid_pairs as f64 / args.nb_key as f64).log2() - ((nb_tries_per_key) as f64).log2());
+    Ok(())
+}
@@ -0,0 +1,101 @@
+use std::fs::File;
+use std::io;
+use std::io::BufReader;
+use std::path::PathBuf;
+
+use clap::Parser;
+use rand::SeedableRng;
+use rand_xoshiro::Xoshiro256StarStar;
+use rayon::prelude::*;
+
+use ciphers::ciphers::gift::{GIFT128, GIFT64};
+use ciphers::differential_characteristics::sk_gift::SingleKeyGiftDifferentialCharacteristic;
+use ciphers::differential_characteristics::validation::to_io_error;
+use ciphers::gift_common::GiftBlock;
+use ciphers::evaluators::evaluate_differential_characteristic;
+use ciphers::ExpandedBlockCipher;
+
+#[derive(Copy, Clone, clap::ValueEnum)]
+enum Version {
+    V64,
+    V128,
+}
+
+#[derive(Parser)]
+struct Args {
+    #[arg(short, long)]
+    version: Version,
+    #[arg(short('k'), long, default_value_t = 8)]
+    nb_key: usize,
+    #[arg(short, long)]
+    nb_tries_per_key: Option<usize>,
+    #[arg(short, long)]
+    path: PathBuf,
+}
+
+fn evaluate<B, E>(args: &Args, dc: &SingleKeyGiftDifferentialCharacteristic, expand: impl Fn(u128) -> E)
+where
+    B: GiftBlock + PartialEq + Send + Sync,
+    for<'a> &'a B: std::ops::BitXor<&'a B, Output = B>,
+    E: ExpandedBlockCipher<B> + Sync,
+{
+    let seed = [
+        6, 3, 14, 7, 11, 12, 0, 15, 8, 2, 1, 10, 4, 5, 9, 13,
+        14, 9, 6, 3, 7, 2, 8, 11, 10, 5, 0, 15, 1, 4, 13, 12
+    ];
+
+    let mut rand = Xoshiro256StarStar::from_seed(seed);
+
+    let input_difference = B::from_nibbles(dc.x.first().unwrap());
+    let output_difference = B::from_nibbles(dc.x.last().unwrap());
+
+    let mut number_of_valid_pairs: usize = 0;
+    let nb_tries_per_key = args.nb_tries_per_key.unwrap_or(1 << (dc.objective + 2));
+
+    for key_no in 0..args.nb_key {
+        let key = expand(u128::random(&mut rand));
+        let generator = std::iter::repeat_with(|| B::random(&mut rand));
+        let number_of_valid_pairs_for_key: usize = generator.take(nb_tries_per_key)
+            .par_bridge()
+            .map(|p0| evaluate_differential_characteristic(
+                &key,
+                p0,
+                &input_difference,
+                &output_difference,
+                &key
+            ))
+            .fold(|| 0usize, |a, b| a + b)
+            .sum();
+
+        number_of_valid_pairs += number_of_valid_pairs_for_key;
+        println!("Random Key {} - {}/{} : 2^{{{}}}", key_no, number_of_valid_pairs_for_key, nb_tries_per_key, (number_of_valid_pairs_for_key as f64).log2() - ((nb_tries_per_key) as f64).log2());
+    }
+
+    println!("Total - {}/{} : 2^{{{}}}", number_of_valid_pairs, nb_tries_per_key * args.nb_key, (number_of_valid_pairs as f64).log2() - ((nb_tries_per_key * args.nb_key) as f64).log2());
+    println!("Mean  - {}/{} : 2^{{{}}}", number_of_valid_pairs as f64 / args.nb_key as f64, nb_tries_per_key, (number_of_valid_pairs as f64 / args.nb_key as f64).log2() - ((nb_tries_per_key) as f64).log2());
+}
+
+fn main() -> io::Result<()> {
+    let args: Args = Args::parse();
+
+    let path = File::open(&args.path).unwrap();
+    let reader = BufReader::new(path);
+    let dc: SingleKeyGiftDifferentialCharacteristic = serde_json::de::from_reader(reader)?;
+    let nibbles = match args.version {
+        Version::V64 => 16,
+        Version::V128 => 32,
+    };
+    dc.validate(nibbles).map_err(to_io_error)?;
+
+    match args.version {
+        Version::V64 => {
+            let cipher = GIFT64::with_rounds(dc.x.len() - 1);
+            evaluate::<u64, _>(&args, &dc, |key| cipher.expand(key));
+        }
+        Version::V128 => {
+            let cipher = GIFT128::with_rounds(dc.x.len() - 1);
+            evaluate::<u128, _>(&args, &dc, |key| cipher.expand(key));
+        }
+    }
+    Ok(())
+}
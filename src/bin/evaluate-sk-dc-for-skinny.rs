@@ -8,19 +8,12 @@ use rand::{RngCore, SeedableRng};
 use rand_xoshiro::Xoshiro256StarStar;
 
 use ciphers::skinny::SKINNY;
-
-use crate::ciphers::SymmetricCipher;
-use crate::differential_characteristics::sk_skinny::SingleKeySkinnyDifferentialCharacteristic;
-use crate::matrix::Matrix;
-
-#[path = "../matrix.rs"]
-mod matrix;
-#[path = "../ciphers/mod.rs"]
-mod ciphers;
-#[path = "../lfsr.rs"]
-mod lfsr;
-#[path = "../differential_characteristics/mod.rs"]
-mod differential_characteristics;
+use ciphers::SymmetricCipher;
+use ciphers::differential_characteristics::sk_skinny::SingleKeySkinnyDifferentialCharacteristic;
+use ciphers::differential_characteristics::validation::to_io_error;
+use ciphers::evaluators::{TrailStatistics, count_followed_rounds, trail_states};
+use ciphers::matrix::Matrix;
+use ciphers::sbox::Sbox;
 
 #[derive(Copy, Clone, clap::ValueEnum)]
 enum Version {
@@ -38,6 +31,9 @@ struct Args {
     nb_tries_per_key: Option<usize>,
     #[arg(short, long)]
     path: PathBuf,
+    /// Records how many rounds each pair follows the characteristic for, instead of checking the output difference only
+    #[arg(long)]
+    per_round: bool,
 }
 
 fn main() -> io::Result<()> {
@@ -52,11 +48,24 @@ fn main() -> io::Result<()> {
     let path = File::open(&args.path).unwrap();
     let reader = BufReader::new(path);
     let dc: SingleKeySkinnyDifferentialCharacteristic = serde_json::de::from_reader(reader)?;
+    let bits = match args.version {
+        Version::V64 => 4,
+        Version::V128 => 8,
+    };
+    dc.validate(bits).map_err(to_io_error)?;
 
     let (cipher, mask) = match args.version {
         Version::V64 => (SKINNY::v64_with_rounds(dc.x.len() - 1), 0xF),
         Version::V128 => (SKINNY::v128_with_rounds(dc.x.len() - 1), 0xFF),
     };
+    let sbox = match args.version {
+        Version::V64 => Sbox::skinny_64(),
+        Version::V128 => Sbox::skinny_128(),
+    };
+    let predicted = dc.theoretical_probability(&sbox);
+    if !predicted.matches_objective(dc.objective) {
+        eprintln!("Warning : the DDT weight {} differs from the objective {}", predicted.weight(), dc.objective);
+    }
 
     let input_difference = dc.x.first().unwrap().iter().flatten().cloned().collect::<Vec<_>>();
     let input_difference = Matrix::new(4, 4, input_difference);
@@ -64,6 +73,10 @@ fn main() -> io::Result<()> {
     let output_difference = dc.x.last().unwrap().iter().flatten().cloned().collect::<Vec<_>>();
     let output_difference = Matrix::new(4, 4, output_difference);
 
+    let (x_states, sc_states) = (trail_states(&dc.x), trail_states(&dc.sc));
+    let rounds = dc.x.len() - 1;
+    let mut statistics = TrailStatistics::new(rounds);
+
     let mut number_of_valid_pairs: usize = 0;
     let nb_tries_per_key = args.nb_tries_per_key.unwrap_or(1 << (dc.objective + 2));
 
@@ -73,22 +86,35 @@ fn main() -> io::Result<()> {
             key.push(rand.next_u64() as u8 & mask);
         }
         let key = Matrix::new(1, 16, key);
+        let expanded_key = cipher.expand(&key);
         for _ in 0..nb_tries_per_key {
             let mut p_values = Vec::with_capacity(16);
             for _ in 0..16 {
                 p_values.push(rand.next_u64() as u8 & mask)
             }
             let mut p0 = Matrix::new(4, 4, p_values);
-            let mut p1 = &p0 ^ &input_difference;
-            cipher.cipher(&key, &mut p0);
-            cipher.cipher(&key, &mut p1);
-            let d_out = &p0 ^ &p1;
-            if output_difference == d_out {
+            let valid = if args.per_round {
+                let followed_rounds = count_followed_rounds(&expanded_key, p0, &x_states, &sc_states, &expanded_key);
+                statistics.record(followed_rounds);
+                followed_rounds == rounds
+            } else {
+                let mut p1 = &p0 ^ &input_difference;
+                cipher.cipher(&key, &mut p0);
+                cipher.cipher(&key, &mut p1);
+                let d_out = &p0 ^ &p1;
+                output_difference == d_out
+            };
+            if valid {
                 number_of_valid_pairs += 1;
             }
         }
     }
 
+    if args.per_round {
+        println!("{}", statistics.report(&predicted));
+    }
+
     println!("{}/{} : {}", number_of_valid_pairs, nb_tries_per_key * args.nb_key, (number_of_valid_pairs as f64).log2() - ((nb_tries_per_key * args.nb_key) as f64).log2());
+    println!("Predicted : {}", predicted);
     Ok(())
 }
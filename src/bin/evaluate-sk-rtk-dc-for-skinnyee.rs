@@ -4,31 +4,20 @@ use std::io::BufReader;
 use std::path::PathBuf;
 
 use clap::Parser;
-use rand::{SeedableRng};
+use rand::SeedableRng;
 use rand_chacha::ChaCha8Rng;
 use rayon::prelude::*;
 
-use crate::ciphers::skinnyee::SKINNYee;
-use crate::differential_characteristics::sk_rtk_skinnyee::SingleKeyRelatedTweakeySkinnyEEDifferentialCharacteristic;
-use crate::matrix::Matrix;
-use crate::skinnyee_common::{compute_tk_xor_tweakey_difference, evaluate_differential_characteristic, fill_random_key_and_tweakey};
-use crate::skinnyee_plaintext_generator::SkinnyeePlaintextGenerator;
-
-#[path = "../matrix.rs"]
-mod matrix;
-#[path = "../ciphers/mod.rs"]
-mod ciphers;
-#[path = "../lfsr.rs"]
-mod lfsr;
-#[path = "../differential_characteristics/mod.rs"]
-mod differential_characteristics;
-
-#[path = "../skinnyee_plaintext_generator.rs"]
-mod skinnyee_plaintext_generator;
-
-#[path = "../skinnyee_common.rs"]
-mod skinnyee_common;
-
+use ciphers::skinnyee::SKINNYee;
+use ciphers::differential_characteristics::sk_rtk_skinnyee::SingleKeyRelatedTweakeySkinnyEEDifferentialCharacteristic;
+use ciphers::differential_characteristics::validation::to_io_error;
+use ciphers::ciphers::packed::pack;
+use ciphers::ciphers::bitsliced::BitsliceWord;
+use ciphers::matrix::Matrix;
+use ciphers::sbox::Sbox;
+use ciphers::evaluators::{TrailStatistics, count_followed_rounds, evaluate_differential_characteristic_batch, trail_states};
+use ciphers::skinnyee_common::{compute_tk_xor_tweakey_difference, fill_random_key_and_tweakey};
+use ciphers::skinnyee_plaintext_generator::{SkinnyeeBitslicedPlaintextGenerator, SkinnyeePlaintextGenerator};
 
 #[derive(Parser)]
 struct Args {
@@ -38,6 +27,9 @@ struct Args {
     nb_tries_per_key: Option<usize>,
     #[arg(short, long)]
     path: PathBuf,
+    /// Records how many rounds each pair follows the characteristic for, instead of checking the output difference only
+    #[arg(long)]
+    per_round: bool,
 }
 
 fn main() -> io::Result<()> {
@@ -52,14 +44,25 @@ fn main() -> io::Result<()> {
     let path = File::open(&args.path).unwrap();
     let reader = BufReader::new(path);
     let dc: SingleKeyRelatedTweakeySkinnyEEDifferentialCharacteristic = serde_json::de::from_reader(reader)?;
+    if let Err(error) = dc.first_tweakey_inconsistency() {
+        eprintln!("First inconsistency : {}", error);
+    }
+    dc.validate().map_err(to_io_error)?;
 
     let (cipher, mask) = (SKINNYee::with_rounds(dc.x.len() - 1), 0xF);
+    let sbox = Sbox::skinny_64();
+    let predicted = dc.theoretical_probability(&sbox);
+    if !predicted.matches_objective(dc.objective) {
+        eprintln!("Warning : the DDT weight {} differs from the objective {}", predicted.weight(), dc.objective);
+    }
 
     let input_difference = dc.x.first().unwrap().iter().flatten().cloned().collect::<Vec<_>>();
     let input_difference = Matrix::new(4, 4, input_difference);
+    let input_difference = pack(&input_difference);
 
     let output_difference = dc.x.last().unwrap().iter().flatten().cloned().collect::<Vec<_>>();
     let output_difference = Matrix::new(4, 4, output_difference);
+    let output_difference = pack(&output_difference);
 
     let tk0_difference = dc.tk[0].first().unwrap().clone();
     let tk0_difference = Matrix::new(4, 4, tk0_difference);
@@ -73,8 +76,12 @@ fn main() -> io::Result<()> {
     let tk3_difference = dc.tk[3].first().unwrap().clone();
     let tk3_difference = Matrix::new(4, 4, tk3_difference);
 
+    let (x_states, sc_states) = (trail_states(&dc.x), trail_states(&dc.sc));
+    let rounds = dc.x.len() - 1;
+    let mut statistics = TrailStatistics::new(rounds);
+
     let mut number_of_valid_pairs: usize = 0;
-    let nb_tries_per_key = args.nb_tries_per_key.unwrap_or(1 << (dc.objective + 2));
+    let nb_tries_per_key = args.nb_tries_per_key.unwrap_or(1 << (dc.objective + 2)).next_multiple_of(u64::LANES);
 
     let mut key_and_tweakey = vec![0; 100];
     for key_no in 0..args.nb_key {
@@ -88,24 +95,45 @@ fn main() -> io::Result<()> {
             &tk3_difference
         );
 
-        let generator = SkinnyeePlaintextGenerator::new(&mut rand);
-        let number_of_valid_pairs_for_key: usize = generator.take(nb_tries_per_key)
-            .par_bridge()
-            .map(|p0| evaluate_differential_characteristic(
-                &cipher, &key_and_tweakey,
-                p0,
-                &input_difference,
-                &output_difference,
-                &tk_xor_tke0
-            ))
-            .fold(|| 0usize, |a, b| a + b)
-            .sum();
+        let key_and_tweakey = cipher.expand(&key_and_tweakey);
+        let tk_xor_tke0 = cipher.expand(&tk_xor_tke0);
+        let number_of_valid_pairs_for_key = if args.per_round {
+            let generator = SkinnyeePlaintextGenerator::new(&mut rand);
+            let statistics_for_key = generator.take(nb_tries_per_key)
+                .par_bridge()
+                .map(|p0| count_followed_rounds(&key_and_tweakey, p0, &x_states, &sc_states, &tk_xor_tke0))
+                .fold(|| TrailStatistics::new(rounds), |mut statistics, followed_rounds| {
+                    statistics.record(followed_rounds);
+                    statistics
+                })
+                .reduce(|| TrailStatistics::new(rounds), |a, b| a.merge(&b));
+            statistics = statistics.merge(&statistics_for_key);
+            statistics_for_key.reaching(rounds)
+        } else {
+            let generator = SkinnyeeBitslicedPlaintextGenerator::<u64>::new(&mut rand);
+            generator.take(nb_tries_per_key / u64::LANES)
+                .par_bridge()
+                .map(|p0| evaluate_differential_characteristic_batch(
+                    &key_and_tweakey,
+                    p0,
+                    input_difference,
+                    output_difference,
+                    &tk_xor_tke0
+                ))
+                .fold(|| 0usize, |a, mask| a + mask.count_ones() as usize)
+                .sum()
+        };
 
         number_of_valid_pairs += number_of_valid_pairs_for_key;
         println!("Random Key {} - {}/{} : 2^{{{}}}", key_no, number_of_valid_pairs_for_key, nb_tries_per_key, (number_of_valid_pairs_for_key as f64).log2() - ((nb_tries_per_key) as f64).log2());
     }
 
+    if args.per_round {
+        println!("{}", statistics.report(&predicted));
+    }
+
     println!("Total - {}/{} : 2^{{{}}}", number_of_valid_pairs, nb_tries_per_key * args.nb_key, (number_of_valid_pairs as f64).log2() - ((nb_tries_per_key * args.nb_key) as f64).log2());
     println!("Mean  - {}/{} : 2^{{{}}}", number_of_valid_pairs as f64 / args.nb_key as f64, nb_tries_per_key, (number_of_valid_pairs as f64 / args.nb_key as f64).log2() - ((nb_tries_per_key) as f64).log2());
+    println!("Predicted : {}", predicted);
     Ok(())
 }
@@ -8,30 +8,16 @@ use rand::{SeedableRng};
 use rand_chacha::ChaCha8Rng;
 use rayon::prelude::*;
 
-use crate::ciphers::skinnyee::SKINNYee;
-use crate::differential_characteristics::sk_boom_rtk_skinnyee::SingleKeyRelatedTweakeySkinnyEEBoomerangCharacteristic;
-use crate::matrix::Matrix;
-use crate::skinnyee_boomerang_cli_args::Args;
-use crate::skinnyee_common::{compute_tk_xor_tweakey_difference, evaluate_boomerang, fill_random_key_and_tweakey};
-use crate::skinnyee_plaintext_generator::SkinnyeePlaintextGenerator;
-
-#[path = "../matrix.rs"]
-mod matrix;
-#[path = "../ciphers/mod.rs"]
-mod ciphers;
-#[path = "../lfsr.rs"]
-mod lfsr;
-#[path = "../differential_characteristics/mod.rs"]
-mod differential_characteristics;
-
-#[path = "../skinnyee_boomerang_cli_args.rs"]
-mod skinnyee_boomerang_cli_args;
-
-#[path = "../skinnyee_plaintext_generator.rs"]
-mod skinnyee_plaintext_generator;
-
-#[path = "../skinnyee_common.rs"]
-mod skinnyee_common;
+use ciphers::skinnyee::SKINNYee;
+use ciphers::differential_characteristics::sk_boom_rtk_skinnyee::SingleKeyRelatedTweakeySkinnyEEBoomerangCharacteristic;
+use ciphers::differential_characteristics::validation::to_io_error;
+use ciphers::ciphers::packed::pack;
+use ciphers::ciphers::bitsliced::BitsliceWord;
+use ciphers::matrix::Matrix;
+use ciphers::skinnyee_boomerang_cli_args::Args;
+use ciphers::evaluators::evaluate_boomerang_batch;
+use ciphers::skinnyee_common::{compute_tk_xor_tweakey_difference, fill_random_key_and_tweakey};
+use ciphers::skinnyee_plaintext_generator::SkinnyeeBitslicedPlaintextGenerator;
 
 fn main() -> io::Result<()> {
     let args: Args = Args::parse();
@@ -46,40 +32,44 @@ fn main() -> io::Result<()> {
     let path = File::open(&args.path).unwrap();
     let reader = BufReader::new(path);
     let dc: SingleKeyRelatedTweakeySkinnyEEBoomerangCharacteristic = serde_json::de::from_reader(reader)?;
+    dc.validate().map_err(to_io_error)?;
 
-    let (cipher, mask) = (SKINNYee::with_rounds(dc.rm), 0xF);
+    let (cipher, mask) = (SKINNYee::rounds(dc.r0 - dc.rm..dc.r0), 0xF);
 
     let e0_input_difference = dc.e0_em.x[dc.r0 - dc.rm].iter().flatten().cloned().collect::<Vec<_>>();
     let e0_input_difference = Matrix::new(4, 4, e0_input_difference);
+    let e0_input_difference = pack(&e0_input_difference);
 
     let e1_output_difference = dc.em_e1.x[dc.rm].iter().flatten().cloned().collect::<Vec<_>>();
     let e1_output_difference = Matrix::new(4, 4, e1_output_difference);
+    let e1_output_difference = pack(&e1_output_difference);
+
+    let mut e0_tks_difference = Vec::with_capacity(16 * 4);
+    e0_tks_difference.extend(&dc.e0_em.tk[0][dc.r0 - dc.rm]);
+    e0_tks_difference.extend(&dc.e0_em.tk[1][dc.r0 - dc.rm]);
+    e0_tks_difference.extend(&dc.e0_em.tk[2][dc.r0 - dc.rm]);
+    e0_tks_difference.extend(&dc.e0_em.tk[3][dc.r0 - dc.rm]);
+
+    let e0_tks_difference = Matrix::new(16, 4, e0_tks_difference);
+    let e0_tks_difference = cipher.inv_nr_tweak_key_schedule(&e0_tks_difference, dc.r0 - dc.rm)
+        .last()
+        .unwrap()
+        .clone();
+
+    let mut e1_tks_difference = Vec::with_capacity(16 * 4);
+    e1_tks_difference.extend(&dc.em_e1.tk[0][0]);
+    e1_tks_difference.extend(&dc.em_e1.tk[1][0]);
+    e1_tks_difference.extend(&dc.em_e1.tk[2][0]);
+    e1_tks_difference.extend(&dc.em_e1.tk[3][0]);
+
+    let e1_tks_difference = Matrix::new(16, 4, e1_tks_difference);
+    let e1_tks_difference = cipher.inv_nr_tweak_key_schedule(&e1_tks_difference, dc.r0 - dc.rm)
+        .last()
+        .unwrap()
+        .clone();
 
-    let e0_tk0_difference = dc.e0_em.tk[0][dc.r0 - dc.rm].clone();
-    let e0_tk0_difference = Matrix::new(4, 4, e0_tk0_difference);
-
-    let e0_tk1_difference = dc.e0_em.tk[1][dc.r0 - dc.rm].clone();
-    let e0_tk1_difference = Matrix::new(4, 4, e0_tk1_difference);
-
-    let e0_tk2_difference = dc.e0_em.tk[2][dc.r0 - dc.rm].clone();
-    let e0_tk2_difference = Matrix::new(4, 4, e0_tk2_difference);
-
-    let e0_tk3_difference = dc.e0_em.tk[3][dc.r0 - dc.rm].clone();
-    let e0_tk3_difference = Matrix::new(4, 4, e0_tk3_difference);
-
-    let e1_tk0_difference = dc.em_e1.tk[0][0].clone();
-    let e1_tk0_difference = Matrix::new(4, 4, e1_tk0_difference);
-
-    let e1_tk1_difference = dc.em_e1.tk[1][0].clone();
-    let e1_tk1_difference = Matrix::new(4, 4, e1_tk1_difference);
-
-    let e1_tk2_difference = dc.em_e1.tk[2][0].clone();
-    let e1_tk2_difference = Matrix::new(4, 4, e1_tk2_difference);
-
-    let e1_tk3_difference = dc.em_e1.tk[3][0].clone();
-    let e1_tk3_difference = Matrix::new(4, 4, e1_tk3_difference);
     let mut number_of_valid_pairs: usize = 0;
-    let nb_tries_per_key = args.nb_tries_per_key.unwrap_or(1 << (dc.e0_em.objective + dc.em_e1.objective + 2));
+    let nb_tries_per_key = args.nb_tries_per_key.unwrap_or(1 << (dc.e0_em.objective + dc.em_e1.objective + 2)).next_multiple_of(u64::LANES);
 
     let mut key_and_tweakey = vec![0; 100];
     for key_no in 0..args.nb_key {
@@ -87,40 +77,44 @@ fn main() -> io::Result<()> {
         let key_and_tweakey = Matrix::new(25, 4, key_and_tweakey.clone());
         let tk_xor_tke0 = compute_tk_xor_tweakey_difference(
             &key_and_tweakey,
-            &e0_tk0_difference,
-            &e0_tk1_difference,
-            &e0_tk2_difference,
-            &e0_tk3_difference
+            &e0_tks_difference[1],
+            &e0_tks_difference[2],
+            &e0_tks_difference[3],
+            &e0_tks_difference[4]
         );
         let tk_xor_tke1 = compute_tk_xor_tweakey_difference(
             &key_and_tweakey,
-            &e1_tk0_difference,
-            &e1_tk1_difference,
-            &e1_tk2_difference,
-            &e1_tk3_difference
+            &e1_tks_difference[1],
+            &e1_tks_difference[2],
+            &e1_tks_difference[3],
+            &e1_tks_difference[4]
         );
         let tk_xor_tke0_xor_tke1 = compute_tk_xor_tweakey_difference(
             &tk_xor_tke0,
-            &e1_tk0_difference,
-            &e1_tk1_difference,
-            &e1_tk2_difference,
-            &e1_tk3_difference
+            &e1_tks_difference[1],
+            &e1_tks_difference[2],
+            &e1_tks_difference[3],
+            &e1_tks_difference[4]
         );
-        let progress_bar = ProgressBar::new(nb_tries_per_key as u64)
+        let key_and_tweakey = cipher.expand(&key_and_tweakey);
+        let tk_xor_tke0 = cipher.expand(&tk_xor_tke0);
+        let tk_xor_tke1 = cipher.expand(&tk_xor_tke1);
+        let tk_xor_tke0_xor_tke1 = cipher.expand(&tk_xor_tke0_xor_tke1);
+        let progress_bar = ProgressBar::new((nb_tries_per_key / u64::LANES) as u64)
             .with_style(ProgressStyle::with_template("{wide_bar} {pos}/{len} {eta}").unwrap());
-        let generator = SkinnyeePlaintextGenerator::new(&mut rand);
-        let number_of_valid_pairs_for_key: usize = generator.take(nb_tries_per_key)
+        let generator = SkinnyeeBitslicedPlaintextGenerator::<u64>::new(&mut rand);
+        let number_of_valid_pairs_for_key: usize = generator.take(nb_tries_per_key / u64::LANES)
             .par_bridge()
             .progress_with(progress_bar)
-            .map(|p0| evaluate_boomerang(
-                &cipher, &key_and_tweakey, p0,
-                &e0_input_difference,
-                &e1_output_difference,
+            .map(|p0| evaluate_boomerang_batch(
+                &key_and_tweakey, p0,
+                e0_input_difference,
+                e1_output_difference,
                 &tk_xor_tke0,
                 &tk_xor_tke1,
                 &tk_xor_tke0_xor_tke1
             ))
-            .fold(|| 0usize, |a, b| a + b)
+            .fold(|| 0usize, |a, mask| a + mask.count_ones() as usize)
             .sum();
 
         number_of_valid_pairs += number_of_valid_pairs_for_key;
@@ -0,0 +1,279 @@
+use crate::ciphers::{ExpandedBlockCipher, SymmetricCipher};
+use crate::matrix::Matrix;
+
+// Data representation
+// The state is a 4x4 byte matrix filled column by column: byte i of a block is cell (i % 4, i / 4).
+
+const NR: usize = 10;
+
+pub(crate) const SBOX: [u8; 256] = sbox();
+
+const INV_SBOX: [u8; 256] = inv_sbox(&SBOX);
+
+const RCON: [u8; NR] = [
+    0x01, 0x02, 0x04, 0x08, 0x10, 0x20, 0x40, 0x80, 0x1b, 0x36
+];
+
+/// Multiplication in GF(2^8) modulo x^8 + x^4 + x^3 + x + 1.
+const fn gf_mul(a: u8, b: u8) -> u8 {
+    let (mut a, mut b, mut product) = (a, b, 0);
+    while b != 0 {
+        if b & 1 == 1 {
+            product ^= a;
+        }
+        a = (a << 1) ^ if a & 0x80 != 0 { 0x1b } else { 0 };
+        b >>= 1;
+    }
+    product
+}
+
+/// Inverse in GF(2^8) followed by the affine map.
+const fn sbox() -> [u8; 256] {
+    let mut table = [0; 256];
+    let mut x = 0;
+    while x < 256 {
+        // x^254 = x^-1, with 0 mapped to 0
+        let mut inverse = 1;
+        let mut i = 0;
+        while i < 254 {
+            inverse = gf_mul(inverse, x as u8);
+            i += 1;
+        }
+        let b = if x == 0 { 0 } else { inverse };
+        table[x] = b ^ b.rotate_left(1) ^ b.rotate_left(2) ^ b.rotate_left(3) ^ b.rotate_left(4) ^ 0x63;
+        x += 1;
+    }
+    table
+}
+
+const fn inv_sbox(sbox: &[u8; 256]) -> [u8; 256] {
+    let mut table = [0; 256];
+    let mut x = 0;
+    while x < 256 {
+        table[sbox[x] as usize] = x as u8;
+        x += 1;
+    }
+    table
+}
+
+/// AES-128 reduced to `r` rounds, with or without the MixColumns of the last round (the full
+/// cipher has 10 rounds and no last MixColumns).
+pub struct AES {
+    r: usize,
+    last_mix_columns: bool,
+}
+
+impl Default for AES {
+    fn default() -> AES {
+        AES::with_rounds(NR, false)
+    }
+}
+
+impl AES {
+    pub fn with_rounds(r: usize, last_mix_columns: bool) -> AES {
+        assert!((1..=NR).contains(&r));
+        AES { r, last_mix_columns }
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Matrix<u8> {
+        assert_eq!(bytes.len(), 16);
+        Matrix::new(4, 4, (0..16).map(|i| bytes[4 * (i % 4) + i / 4]).collect())
+    }
+
+    pub fn to_bytes(state: &Matrix<u8>) -> Vec<u8> {
+        (0..16).map(|i| state[(i % 4, i / 4)]).collect()
+    }
+
+    /// Round keys 0 to r of a 16-byte key, as states.
+    fn key_schedule(&self, key: &[u8]) -> Vec<Matrix<u8>> {
+        assert_eq!(key.len(), 16);
+        let mut words = key.chunks(4)
+            .map(|word| [word[0], word[1], word[2], word[3]])
+            .collect::<Vec<_>>();
+        for i in 4..4 * (self.r + 1) {
+            let mut word = words[i - 1];
+            if i % 4 == 0 {
+                word.rotate_left(1);
+                word = word.map(|b| SBOX[b as usize]);
+                word[0] ^= RCON[i / 4 - 1];
+            }
+            words.push(core::array::from_fn(|j| word[j] ^ words[i - 4][j]));
+        }
+        words.chunks(4)
+            .map(|round_key| Self::from_bytes(round_key.concat().as_slice()))
+            .collect()
+    }
+
+    /// Expands a key, given as a state (see `from_bytes`), once.
+    pub fn expand(&self, key: &Matrix<u8>) -> ExpandedKey<'_> {
+        ExpandedKey { cipher: self, round_keys: self.key_schedule(&Self::to_bytes(key)) }
+    }
+
+    #[inline]
+    fn has_mix_columns(&self, round_num: usize) -> bool {
+        round_num < self.r || self.last_mix_columns
+    }
+
+    #[inline]
+    pub(crate) fn sub_bytes(internal_state: &mut Matrix<u8>) {
+        internal_state.iter_mut()
+            .for_each(|it| *it = SBOX[*it as usize])
+    }
+
+    #[inline]
+    pub(crate) fn inv_sub_bytes(internal_state: &mut Matrix<u8>) {
+        internal_state.iter_mut()
+            .for_each(|it| *it = INV_SBOX[*it as usize])
+    }
+
+    #[inline]
+    pub(crate) fn shift_rows(internal_state: &mut Matrix<u8>) {
+        for i in 1..4 {
+            let row = (0..4).map(|j| internal_state[(i, (j + i) % 4)]).collect::<Vec<_>>();
+            (0..4).for_each(|j| internal_state[(i, j)] = row[j]);
+        }
+    }
+
+    #[inline]
+    pub(crate) fn inv_shift_rows(internal_state: &mut Matrix<u8>) {
+        for i in 1..4 {
+            let row = (0..4).map(|j| internal_state[(i, (j + 4 - i) % 4)]).collect::<Vec<_>>();
+            (0..4).for_each(|j| internal_state[(i, j)] = row[j]);
+        }
+    }
+
+    #[inline]
+    fn multiply_columns(internal_state: &mut Matrix<u8>, coefficients: [u8; 4]) {
+        for j in 0..4 {
+            let column = (0..4).map(|i| internal_state[(i, j)]).collect::<Vec<_>>();
+            for i in 0..4 {
+                internal_state[(i, j)] = (0..4).fold(0, |acc, k| acc ^ gf_mul(coefficients[(k + 4 - i) % 4], column[k]));
+            }
+        }
+    }
+
+    /// Multiplication of each column by the circulant matrix (2, 3, 1, 1).
+    #[inline]
+    pub(crate) fn mix_columns(internal_state: &mut Matrix<u8>) {
+        Self::multiply_columns(internal_state, [2, 3, 1, 1]);
+    }
+
+    #[inline]
+    pub(crate) fn inv_mix_columns(internal_state: &mut Matrix<u8>) {
+        Self::multiply_columns(internal_state, [14, 11, 13, 9]);
+    }
+}
+
+/// AES instance with its round keys already computed.
+pub struct ExpandedKey<'c> {
+    cipher: &'c AES,
+    round_keys: Vec<Matrix<u8>>,
+}
+
+impl ExpandedBlockCipher<Matrix<u8>> for ExpandedKey<'_> {
+    fn encrypt_block(&self, plaintext: &mut Matrix<u8>) {
+        *plaintext ^= &self.round_keys[0];
+        for round_num in 1..=self.cipher.r {
+            AES::sub_bytes(plaintext);
+            AES::shift_rows(plaintext);
+            if self.cipher.has_mix_columns(round_num) {
+                AES::mix_columns(plaintext);
+            }
+            *plaintext ^= &self.round_keys[round_num];
+        }
+    }
+
+    fn decrypt_block(&self, ciphertext: &mut Matrix<u8>) {
+        for round_num in (1..=self.cipher.r).rev() {
+            *ciphertext ^= &self.round_keys[round_num];
+            if self.cipher.has_mix_columns(round_num) {
+                AES::inv_mix_columns(ciphertext);
+            }
+            AES::inv_shift_rows(ciphertext);
+            AES::inv_sub_bytes(ciphertext);
+        }
+        *ciphertext ^= &self.round_keys[0];
+    }
+}
+
+/// The key is a state, see `AES::from_bytes`.
+impl SymmetricCipher<Matrix<u8>, Matrix<u8>> for AES {
+    fn cipher(&self, key: &Matrix<u8>, plaintext: &mut Matrix<u8>) {
+        self.expand(key).encrypt_block(plaintext);
+    }
+
+    fn decipher(&self, key: &Matrix<u8>, plaintext: &mut Matrix<u8>) {
+        self.expand(key).decrypt_block(plaintext);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ciphers::aes::{AES, INV_SBOX, SBOX};
+    use crate::ciphers::SymmetricCipher;
+    use crate::evaluators::{evaluate_boomerang, evaluate_differential_characteristic};
+    use crate::matrix::Matrix;
+
+    fn parse_bytes(word: &'static str) -> Vec<u8> {
+        (0..word.len()).step_by(2)
+            .map(|i| u8::from_str_radix(&word[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    fn check_test_vector(key: &'static str, plaintext: &'static str, ciphertext: &'static str) {
+        let aes = AES::default();
+        let key = AES::from_bytes(&parse_bytes(key));
+        let mut state = AES::from_bytes(&parse_bytes(plaintext));
+        aes.cipher(&key, &mut state);
+        assert_eq!(AES::to_bytes(&state), parse_bytes(ciphertext));
+        aes.decipher(&key, &mut state);
+        assert_eq!(AES::to_bytes(&state), parse_bytes(plaintext));
+    }
+
+    #[test]
+    fn test_vectors_aes_128() {
+        check_test_vector("000102030405060708090a0b0c0d0e0f", "00112233445566778899aabbccddeeff", "69c4e0d86a7b0430d8cdb78070b4c55a");
+        check_test_vector("2b7e151628aed2a6abf7158809cf4f3c", "3243f6a8885a308d313198a2e0370734", "3925841d02dc09fbdc118597196a0b32");
+    }
+
+    #[test]
+    fn test_sbox() {
+        assert_eq!(SBOX[..4], [0x63, 0x7c, 0x77, 0x7b]);
+        assert_eq!(SBOX[0xff], 0x16);
+        assert_eq!(INV_SBOX[0x63], 0);
+    }
+
+    #[test]
+    fn test_round_trip_reduced_aes() {
+        let key = AES::from_bytes(&parse_bytes("2b7e151628aed2a6abf7158809cf4f3c"));
+        let plaintext = AES::from_bytes(&parse_bytes("3243f6a8885a308d313198a2e0370734"));
+        for aes in [AES::with_rounds(1, false), AES::with_rounds(4, true), AES::with_rounds(5, false)] {
+            let mut state = plaintext.clone();
+            aes.cipher(&key, &mut state);
+            assert_ne!(state, plaintext);
+            aes.decipher(&key, &mut state);
+            assert_eq!(state, plaintext);
+        }
+    }
+
+    #[test]
+    fn test_generic_evaluators_aes() {
+        let aes = AES::with_rounds(1, true);
+        let key = aes.expand(&AES::from_bytes(&parse_bytes("000102030405060708090a0b0c0d0e0f")));
+        let p0 = AES::from_bytes(&parse_bytes("00112233445566778899aabbccddeeff"));
+
+        // The boomerang returns with probability 1 when the differences of E0 and E1 go through
+        // distinct S-boxes.
+        let mut e0_input_difference = Matrix::new(4, 4, vec![0; 16]);
+        e0_input_difference[(0, 0)] = 0x01;
+        let mut e1_output_difference = Matrix::new(4, 4, vec![0; 16]);
+        e1_output_difference[(1, 1)] = 0x80;
+        AES::shift_rows(&mut e1_output_difference);
+        AES::mix_columns(&mut e1_output_difference);
+        assert_eq!(evaluate_boomerang(&key, p0.clone(), &e0_input_difference, &e1_output_difference, &key, &key, &key), 1);
+
+        let zero = Matrix::new(4, 4, vec![0; 16]);
+        assert_eq!(evaluate_differential_characteristic(&key, p0.clone(), &zero, &zero, &key), 1);
+        assert_eq!(evaluate_differential_characteristic(&key, p0, &e0_input_difference, &zero, &key), 0);
+    }
+}
@@ -0,0 +1,281 @@
+use std::array;
+use std::ops::{BitAnd, BitOr, BitXor, BitXorAssign, Not};
+
+use crate::ciphers::ExpandedBlockCipher;
+use crate::ciphers::packed::PackedRoundKeys;
+
+// Data representation
+// A batch holds W::LANES packed 64-bit states (see `packed`) under the same key.
+// Slice b holds bit b of every packed state: bit j of slices[b] is bit b of the j-th block.
+// Bit b of a packed state is bit (b % 4) of cell b / 4 = 4 * row + col.
+
+/// Machine word used as a set of lanes, one bit per block.
+pub trait BitsliceWord: Copy + PartialEq + BitAnd<Output=Self> + BitOr<Output=Self> + BitXor<Output=Self> + BitXorAssign + Not<Output=Self> {
+    const LANES: usize;
+    const ZERO: Self;
+    const ONES: Self;
+
+    fn bitslice(blocks: &[u64]) -> [Self; 64];
+    fn unbitslice(slices: &[Self; 64]) -> Vec<u64>;
+    fn count_ones(self) -> u32;
+}
+
+/// In-place transposition of a 64x64 bit matrix: bit j of `a[i]` becomes bit i of `a[j]`.
+fn transpose_64(a: &mut [u64; 64]) {
+    let mut j = 32;
+    let mut m = 0x00000000FFFFFFFFu64;
+    while j != 0 {
+        for k in 0..64 {
+            if k & j == 0 {
+                let t = ((a[k] >> j) ^ a[k + j]) & m;
+                a[k + j] ^= t;
+                a[k] ^= t << j;
+            }
+        }
+        j >>= 1;
+        m ^= m << j;
+    }
+}
+
+impl BitsliceWord for u64 {
+    const LANES: usize = 64;
+    const ZERO: u64 = 0;
+    const ONES: u64 = u64::MAX;
+
+    fn bitslice(blocks: &[u64]) -> [u64; 64] {
+        assert_eq!(blocks.len(), Self::LANES);
+        let mut slices = [0; 64];
+        slices.copy_from_slice(blocks);
+        transpose_64(&mut slices);
+        slices
+    }
+
+    fn unbitslice(slices: &[u64; 64]) -> Vec<u64> {
+        let mut blocks = *slices;
+        transpose_64(&mut blocks);
+        blocks.to_vec()
+    }
+
+    fn count_ones(self) -> u32 {
+        u64::count_ones(self)
+    }
+}
+
+impl BitsliceWord for u128 {
+    const LANES: usize = 128;
+    const ZERO: u128 = 0;
+    const ONES: u128 = u128::MAX;
+
+    fn bitslice(blocks: &[u64]) -> [u128; 64] {
+        assert_eq!(blocks.len(), Self::LANES);
+        let low = u64::bitslice(&blocks[..64]);
+        let high = u64::bitslice(&blocks[64..]);
+        array::from_fn(|b| low[b] as u128 | (high[b] as u128) << 64)
+    }
+
+    fn unbitslice(slices: &[u128; 64]) -> Vec<u64> {
+        let mut blocks = u64::unbitslice(&array::from_fn(|b| slices[b] as u64));
+        blocks.extend(u64::unbitslice(&array::from_fn(|b| (slices[b] >> 64) as u64)));
+        blocks
+    }
+
+    fn count_ones(self) -> u32 {
+        u128::count_ones(self)
+    }
+}
+
+#[inline]
+fn slice_index(row: usize, col: usize, bit: usize) -> usize {
+    4 * (4 * row + col) + bit
+}
+
+/// W::LANES 64-bit SKINNY states processed in parallel.
+#[derive(Clone)]
+pub struct BitslicedBatch<W: BitsliceWord> {
+    slices: [W; 64],
+}
+
+impl<W: BitsliceWord> BitslicedBatch<W> {
+    pub fn new(blocks: &[u64]) -> BitslicedBatch<W> {
+        BitslicedBatch { slices: W::bitslice(blocks) }
+    }
+
+    pub fn blocks(&self) -> Vec<u64> {
+        W::unbitslice(&self.slices)
+    }
+
+    /// XORs the same packed value into every lane (round key, difference, ...).
+    #[inline]
+    pub fn xor_packed(&mut self, value: u64) {
+        for b in 0..64 {
+            if (value >> b) & 1 == 1 {
+                self.slices[b] = !self.slices[b];
+            }
+        }
+    }
+
+    /// Mask of the lanes in which `self ^ other` equals the packed `difference`.
+    pub fn lanes_with_difference(&self, other: &BitslicedBatch<W>, difference: u64) -> W {
+        let mut mask = W::ONES;
+        for b in 0..64 {
+            let d = self.slices[b] ^ other.slices[b];
+            mask = mask & if (difference >> b) & 1 == 1 { d } else { !d };
+        }
+        mask
+    }
+
+    #[inline]
+    fn sub_cells(&mut self) {
+        for cell in self.slices.chunks_exact_mut(4) {
+            let (mut x0, mut x1, mut x2, mut x3) = (cell[0], cell[1], cell[2], cell[3]);
+            for it in 0..4 {
+                x0 ^= !(x3 | x2);
+                if it < 3 {
+                    (x0, x1, x2, x3) = (x3, x0, x1, x2);
+                }
+            }
+            cell.copy_from_slice(&[x0, x1, x2, x3]);
+        }
+    }
+
+    #[inline]
+    fn inv_sub_cells(&mut self) {
+        for cell in self.slices.chunks_exact_mut(4) {
+            let (mut x0, mut x1, mut x2, mut x3) = (cell[0], cell[1], cell[2], cell[3]);
+            for it in (0..4).rev() {
+                if it < 3 {
+                    (x0, x1, x2, x3) = (x1, x2, x3, x0);
+                }
+                x0 ^= !(x3 | x2);
+            }
+            cell.copy_from_slice(&[x0, x1, x2, x3]);
+        }
+    }
+
+    #[inline]
+    fn shift_rows(&mut self) {
+        let copy = self.slices;
+        for row in 1..4 {
+            for col in 0..4 {
+                for bit in 0..4 {
+                    self.slices[slice_index(row, col, bit)] = copy[slice_index(row, (col + 4 - row) % 4, bit)];
+                }
+            }
+        }
+    }
+
+    #[inline]
+    fn inv_shift_rows(&mut self) {
+        let copy = self.slices;
+        for row in 1..4 {
+            for col in 0..4 {
+                for bit in 0..4 {
+                    self.slices[slice_index(row, (col + 4 - row) % 4, bit)] = copy[slice_index(row, col, bit)];
+                }
+            }
+        }
+    }
+
+    #[inline]
+    fn mix_columns(&mut self) {
+        for col in 0..4 {
+            for bit in 0..4 {
+                let [i0, i1, i2, i3] = array::from_fn(|row| slice_index(row, col, bit));
+                let (r0, mut r1, mut r2, mut r3) = (self.slices[i0], self.slices[i1], self.slices[i2], self.slices[i3]);
+                r1 ^= r2;
+                r2 ^= r0;
+                r3 ^= r2;
+                (self.slices[i0], self.slices[i1], self.slices[i2], self.slices[i3]) = (r3, r0, r1, r2);
+            }
+        }
+    }
+
+    #[inline]
+    fn inv_mix_columns(&mut self) {
+        for col in 0..4 {
+            for bit in 0..4 {
+                let [i0, i1, i2, i3] = array::from_fn(|row| slice_index(row, col, bit));
+                let (mut r0, r1, mut r2, mut r3) = (self.slices[i0], self.slices[i1], self.slices[i2], self.slices[i3]);
+                r0 ^= r3;
+                r3 ^= r1;
+                r2 ^= r3;
+                (self.slices[i0], self.slices[i1], self.slices[i2], self.slices[i3]) = (r1, r2, r3, r0);
+            }
+        }
+    }
+}
+
+impl<W: BitsliceWord> ExpandedBlockCipher<BitslicedBatch<W>> for PackedRoundKeys {
+    fn encrypt_block(&self, plaintext: &mut BitslicedBatch<W>) {
+        for round_key in self.round_keys() {
+            plaintext.sub_cells();
+            plaintext.xor_packed(*round_key);
+            plaintext.shift_rows();
+            plaintext.mix_columns();
+        }
+    }
+
+    fn decrypt_block(&self, ciphertext: &mut BitslicedBatch<W>) {
+        for round_key in self.round_keys().iter().rev() {
+            ciphertext.inv_mix_columns();
+            ciphertext.inv_shift_rows();
+            ciphertext.xor_packed(*round_key);
+            ciphertext.inv_sub_cells();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ciphers::bitsliced::{BitslicedBatch, BitsliceWord};
+    use crate::ciphers::packed;
+
+    fn blocks(n: usize) -> Vec<u64> {
+        let mut state = 0x06034f957724d19du64;
+        (0..n).map(|_| {
+            state = state.wrapping_mul(0x9e3779b97f4a7c15).rotate_left(17);
+            state
+        }).collect()
+    }
+
+    fn check_operations<W: BitsliceWord>() {
+        let blocks = blocks(W::LANES);
+        let batch = BitslicedBatch::<W>::new(&blocks);
+        assert_eq!(batch.blocks(), blocks);
+
+        let apply = |operation: fn(&mut BitslicedBatch<W>)| {
+            let mut batch = batch.clone();
+            operation(&mut batch);
+            batch.blocks()
+        };
+        let expected = |operation: fn(u64) -> u64| blocks.iter().map(|it| operation(*it)).collect::<Vec<_>>();
+        assert_eq!(apply(BitslicedBatch::sub_cells), expected(packed::sub_cells));
+        assert_eq!(apply(BitslicedBatch::inv_sub_cells), expected(packed::inv_sub_cells));
+        assert_eq!(apply(BitslicedBatch::shift_rows), expected(packed::shift_rows));
+        assert_eq!(apply(BitslicedBatch::inv_shift_rows), expected(packed::inv_shift_rows));
+        assert_eq!(apply(BitslicedBatch::mix_columns), expected(packed::mix_columns));
+        assert_eq!(apply(BitslicedBatch::inv_mix_columns), expected(packed::inv_mix_columns));
+    }
+
+    #[test]
+    fn test_bitsliced_operations_match_packed() {
+        check_operations::<u64>();
+        check_operations::<u128>();
+    }
+
+    #[test]
+    fn test_lanes_with_difference() {
+        let blocks = blocks(64);
+        let difference = 0x0000000000f00002;
+        let mut other_blocks = blocks.iter().map(|it| it ^ difference).collect::<Vec<_>>();
+        other_blocks[5] ^= 1;
+        other_blocks[63] ^= 1 << 63;
+        let batch = BitslicedBatch::<u64>::new(&blocks);
+        let other = BitslicedBatch::<u64>::new(&other_blocks);
+        assert_eq!(batch.lanes_with_difference(&other, difference), !(1 << 5 | 1 << 63));
+
+        let mut shifted = batch.clone();
+        shifted.xor_packed(difference);
+        assert_eq!(batch.lanes_with_difference(&shifted, difference), u64::ONES);
+    }
+}
@@ -0,0 +1,231 @@
+use crate::ciphers::mantis::MIDORI_SBOX;
+use crate::ciphers::{ExpandedBlockCipher, SymmetricCipher, TweakableBlockCipher, tweakey};
+use crate::matrix::Matrix;
+
+const NR: usize = 32;
+
+/// Involutory nibble permutation PN, cell `i` moves to cell `P[i]`.
+const P: [usize; 16] = [
+    15, 12, 13, 14, 10, 9, 8, 11, 6, 5, 4, 7, 1, 2, 3, 0
+];
+
+/// Tweak permutation Q of the tweak schedule.
+const Q: [usize; 16] = [
+    12, 10, 15, 5, 14, 8, 9, 2, 11, 3, 7, 4, 6, 0, 1, 13
+];
+
+/// `a || b`: `a` from a 4-bit LFSR added to cell 4, `b` from a 3-bit LFSR added to cell 5.
+const RC: [u8; NR] = round_constants();
+
+const fn round_constants() -> [u8; NR] {
+    let mut rc = [0; NR];
+    let (mut a, mut b) = (1u8, 1u8);
+    let mut i = 0;
+    while i < NR {
+        rc[i] = a << 4 | b;
+        a = a >> 1 | ((a ^ a >> 1) & 1) << 3;
+        b = b >> 1 | ((b ^ b >> 1) & 1) << 2;
+        i += 1;
+    }
+    rc
+}
+
+/// CRAFT: 64-bit block, 128-bit key `K0 || K1` and 64-bit tweak `T`. Round `i` is MixColumns,
+/// the addition of the constant and of `TK_{i mod 4}`, then PN and SubCells, the last round
+/// stopping after the tweakey addition.
+pub struct CRAFT {
+    r: usize,
+    last_round_linear: bool,
+}
+
+impl Default for CRAFT {
+    fn default() -> CRAFT {
+        CRAFT::with_rounds(NR)
+    }
+}
+
+impl CRAFT {
+    /// `r` rounds, the last one being linear as in the full cipher.
+    pub fn with_rounds(r: usize) -> CRAFT {
+        assert!((1..=NR).contains(&r));
+        CRAFT { r, last_round_linear: true }
+    }
+
+    /// `r` full rounds, as counted by differential characteristics.
+    pub fn full_rounds(r: usize) -> CRAFT {
+        assert!(r <= NR);
+        CRAFT { r, last_round_linear: false }
+    }
+
+    /// Splits the tweakey `T || K0 || K1` into its three lanes.
+    fn split_key(key: &Matrix<u8>) -> (Matrix<u8>, Matrix<u8>, Matrix<u8>) {
+        assert_eq!(key.values.len(), 48);
+        let lane = |i: usize| Matrix::new(4, 4, key.values[16 * i..16 * (i + 1)].to_vec());
+        (lane(0), lane(1), lane(2))
+    }
+
+    /// `TK0 = K0 ^ T`, `TK1 = K1 ^ T`, `TK2 = K0 ^ Q(T)`, `TK3 = K1 ^ Q(T)`.
+    pub fn tweak_schedule(key: &Matrix<u8>) -> [Matrix<u8>; 4] {
+        let (tweak, k0, k1) = Self::split_key(key);
+        let q_tweak = Matrix::new(4, 4, (0..16).map(|i| tweak.values[Q[i]]).collect());
+        [&k0 ^ &tweak, &k1 ^ &tweak, &k0 ^ &q_tweak, &k1 ^ &q_tweak]
+    }
+
+    /// Expands a tweakey `T || K0 || K1` once, so that many blocks can be processed under it.
+    pub fn expand(&self, key: &Matrix<u8>) -> ExpandedKey<'_> {
+        let tweakeys = Self::tweak_schedule(key);
+        let round_keys = (0..self.r)
+            .map(|round_num| {
+                let mut round_key = tweakeys[round_num % 4].clone();
+                round_key[(1, 0)] ^= RC[round_num] >> 4;
+                round_key[(1, 1)] ^= RC[round_num] & 0xF;
+                round_key
+            })
+            .collect();
+        ExpandedKey { cipher: self, round_keys }
+    }
+
+    #[inline]
+    fn is_linear(&self, round_num: usize) -> bool {
+        self.last_round_linear && round_num + 1 == self.r
+    }
+
+    #[inline]
+    fn mix_columns(internal_state: &mut Matrix<u8>) {
+        for j in 0..4 {
+            internal_state[(0, j)] ^= internal_state[(2, j)] ^ internal_state[(3, j)];
+            internal_state[(1, j)] ^= internal_state[(3, j)];
+        }
+    }
+
+    #[inline]
+    fn permute_nibbles(internal_state: &mut Matrix<u8>) {
+        let mut permuted = vec![0; 16];
+        for i in 0..16 {
+            permuted[P[i]] = internal_state.values[i];
+        }
+        internal_state.values = permuted;
+    }
+
+    #[inline]
+    fn sub_cells(internal_state: &mut Matrix<u8>) {
+        internal_state.iter_mut()
+            .for_each(|it| *it = MIDORI_SBOX[*it as usize])
+    }
+}
+
+/// CRAFT instance with its tweakeys and round constants already computed. Every layer is an
+/// involution, so decryption runs the same layers in reverse order.
+pub struct ExpandedKey<'c> {
+    cipher: &'c CRAFT,
+    round_keys: Vec<Matrix<u8>>,
+}
+
+impl ExpandedBlockCipher<Matrix<u8>> for ExpandedKey<'_> {
+    fn encrypt_block(&self, plaintext: &mut Matrix<u8>) {
+        for (round_num, round_key) in self.round_keys.iter().enumerate() {
+            CRAFT::mix_columns(plaintext);
+            *plaintext ^= round_key;
+            if !self.cipher.is_linear(round_num) {
+                CRAFT::permute_nibbles(plaintext);
+                CRAFT::sub_cells(plaintext);
+            }
+        }
+    }
+
+    fn decrypt_block(&self, ciphertext: &mut Matrix<u8>) {
+        for (round_num, round_key) in self.round_keys.iter().enumerate().rev() {
+            if !self.cipher.is_linear(round_num) {
+                CRAFT::sub_cells(ciphertext);
+                CRAFT::permute_nibbles(ciphertext);
+            }
+            *ciphertext ^= round_key;
+            CRAFT::mix_columns(ciphertext);
+        }
+    }
+}
+
+/// The key is the tweakey `T || K0 || K1`, as a 3x16 matrix.
+impl SymmetricCipher<Matrix<u8>, Matrix<u8>> for CRAFT {
+    fn cipher(&self, key: &Matrix<u8>, plaintext: &mut Matrix<u8>) {
+        self.expand(key).encrypt_block(plaintext);
+    }
+
+    fn decipher(&self, key: &Matrix<u8>, plaintext: &mut Matrix<u8>) {
+        self.expand(key).decrypt_block(plaintext);
+    }
+}
+
+impl TweakableBlockCipher for CRAFT {
+    type Key = Matrix<u8>;
+    type Tweak = Matrix<u8>;
+    type Block = Matrix<u8>;
+
+    fn encrypt(&self, key: &Matrix<u8>, tweak: &Matrix<u8>, block: &mut Matrix<u8>) {
+        self.cipher(&tweakey(key, tweak), block);
+    }
+
+    fn decrypt(&self, key: &Matrix<u8>, tweak: &Matrix<u8>, block: &mut Matrix<u8>) {
+        self.decipher(&tweakey(key, tweak), block);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ciphers::craft::{CRAFT, P, RC};
+    use crate::ciphers::TweakableBlockCipher;
+    use crate::matrix::Matrix;
+
+    fn nibbles(seed: u8, n: usize) -> Vec<u8> {
+        (0..n).map(|i| (i as u8).wrapping_mul(seed).wrapping_add(3) & 0xF).collect()
+    }
+
+    #[test]
+    fn test_round_constants() {
+        assert_eq!(RC[..8], [0x11, 0x84, 0x42, 0x25, 0x96, 0xc7, 0x63, 0xb1]);
+        assert_eq!(RC[15], 0x14);
+    }
+
+    #[test]
+    fn test_involutions() {
+        let state = Matrix::new(4, 4, nibbles(7, 16));
+        let mut permuted = state.clone();
+        CRAFT::permute_nibbles(&mut permuted);
+        assert_eq!(P.map(|i| P[i]), core::array::from_fn(|i| i));
+        CRAFT::permute_nibbles(&mut permuted);
+        assert_eq!(permuted, state);
+        let mut mixed = state.clone();
+        CRAFT::mix_columns(&mut mixed);
+        assert_ne!(mixed, state);
+        CRAFT::mix_columns(&mut mixed);
+        assert_eq!(mixed, state);
+    }
+
+    #[test]
+    fn test_round_trip_craft() {
+        let key = Matrix::new(2, 16, nibbles(5, 32));
+        let tweak = Matrix::new(1, 16, nibbles(11, 16));
+        let plaintext = Matrix::new(4, 4, nibbles(13, 16));
+        for craft in [CRAFT::default(), CRAFT::with_rounds(7), CRAFT::full_rounds(7)] {
+            let mut state = plaintext.clone();
+            craft.encrypt(&key, &tweak, &mut state);
+            assert_ne!(state, plaintext);
+            craft.decrypt(&key, &tweak, &mut state);
+            assert_eq!(state, plaintext);
+        }
+    }
+
+    #[test]
+    fn test_tweak_schedule() {
+        let key = Matrix::new(3, 16, [vec![0; 16], nibbles(5, 32)].concat());
+        let [tk0, tk1, tk2, tk3] = CRAFT::tweak_schedule(&key);
+        assert_eq!(tk0, tk2);
+        assert_eq!(tk1, tk3);
+        let mut tweak = vec![0; 16];
+        tweak[0] = 1;
+        let key = Matrix::new(3, 16, [tweak, vec![0; 32]].concat());
+        let [tk0, _, tk2, _] = CRAFT::tweak_schedule(&key);
+        assert_eq!(tk0[(0, 0)], 1);
+        assert_eq!(tk2[(3, 1)], 1);
+    }
+}
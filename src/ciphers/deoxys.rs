@@ -0,0 +1,230 @@
+use crate::ciphers::aes::AES;
+use crate::ciphers::{ExpandedBlockCipher, SymmetricCipher, TweakableBlockCipher, tweakey};
+use crate::lfsr::{LFSR, x};
+use crate::matrix::Matrix;
+
+// Data representation
+// Each 16-byte tweakey lane and the state follow the AES byte order, see `AES::from_bytes`.
+
+const NR: [usize; 2] = [14, 16];
+
+/// Byte permutation of the tweakey lanes, byte `i` of the next lane is byte `H[i]` of the current one.
+const H: [usize; 16] = [
+    1, 6, 11, 12, 5, 10, 15, 0, 9, 14, 3, 4, 13, 2, 7, 8
+];
+
+/// Round constants: successive doublings of 0x2f in GF(2^8).
+const RCON: [u8; 17] = rcon();
+
+const fn rcon() -> [u8; 17] {
+    let mut rcon = [0; 17];
+    let mut value = 0x2fu8;
+    let mut i = 0;
+    while i < 17 {
+        rcon[i] = value;
+        value = (value << 1) ^ if value & 0x80 != 0 { 0x1b } else { 0 };
+        i += 1;
+    }
+    rcon
+}
+
+/// Deoxys-BC-256 (two tweakey lanes, 14 rounds) and Deoxys-BC-384 (three lanes, 16 rounds): AES
+/// rounds keyed with `STK_i = TK1_i ^ TK2_i (^ TK3_i) ^ RC_i`, TK2 and TK3 bytes going through
+/// LFSR2 and LFSR3 before the permutation h at each round.
+pub struct DeoxysBC {
+    tk: usize,
+    r: usize,
+    lfsrs: Vec<LFSR<8>>,
+}
+
+impl DeoxysBC {
+    pub fn v256() -> DeoxysBC {
+        Self::v256_with_rounds(NR[0])
+    }
+
+    pub fn v384() -> DeoxysBC {
+        Self::v384_with_rounds(NR[1])
+    }
+
+    pub fn v256_with_rounds(r: usize) -> DeoxysBC {
+        Self::new(2, r)
+    }
+
+    pub fn v384_with_rounds(r: usize) -> DeoxysBC {
+        Self::new(3, r)
+    }
+
+    fn new(tk: usize, r: usize) -> DeoxysBC {
+        assert!(r < RCON.len());
+        DeoxysBC {
+            tk,
+            r,
+            lfsrs: vec![
+                LFSR::new([x(6), x(5), x(4), x(3), x(2), x(1), x(0), x(7) ^ x(5)]),
+                LFSR::new([x(0) ^ x(6), x(7), x(6), x(5), x(4), x(3), x(2), x(1)]),
+            ],
+        }
+    }
+
+    #[inline]
+    fn h(lane: &[u8]) -> Vec<u8> {
+        H.iter().map(|&i| lane[i]).collect()
+    }
+
+    /// Subtweakeys `STK_0` to `STK_r` of the tweakey `TK1 || ... || TKtk`, one lane per row.
+    pub fn key_schedule(&self, key: &Matrix<u8>) -> Vec<Matrix<u8>> {
+        assert_eq!(key.values.len(), 16 * self.tk);
+        let mut lanes = key.values
+            .chunks(16)
+            .map(|lane| lane.to_vec())
+            .collect::<Vec<_>>();
+        (0..=self.r).map(|round_num| {
+            let mut stk = (0..16)
+                .map(|i| lanes.iter().fold(0, |acc, lane| acc ^ lane[i]))
+                .collect::<Vec<_>>();
+            for i in 0..4 {
+                stk[i] ^= 1 << i;
+                stk[4 + i] ^= RCON[round_num];
+            }
+            for (z, lane) in lanes.iter_mut().enumerate() {
+                if z > 0 {
+                    lane.iter_mut().for_each(|it| *it = self.lfsrs[z - 1].eval(*it as usize) as u8);
+                }
+                *lane = Self::h(lane);
+            }
+            AES::from_bytes(&stk)
+        }).collect()
+    }
+
+    pub fn expand(&self, key: &Matrix<u8>) -> ExpandedKey {
+        ExpandedKey { round_keys: self.key_schedule(key) }
+    }
+}
+
+/// Deoxys-BC instance with its subtweakeys already computed.
+pub struct ExpandedKey {
+    round_keys: Vec<Matrix<u8>>,
+}
+
+impl ExpandedBlockCipher<Matrix<u8>> for ExpandedKey {
+    fn encrypt_block(&self, plaintext: &mut Matrix<u8>) {
+        *plaintext ^= &self.round_keys[0];
+        for round_key in &self.round_keys[1..] {
+            AES::sub_bytes(plaintext);
+            AES::shift_rows(plaintext);
+            AES::mix_columns(plaintext);
+            *plaintext ^= round_key;
+        }
+    }
+
+    fn decrypt_block(&self, ciphertext: &mut Matrix<u8>) {
+        for round_key in self.round_keys[1..].iter().rev() {
+            *ciphertext ^= round_key;
+            AES::inv_mix_columns(ciphertext);
+            AES::inv_shift_rows(ciphertext);
+            AES::inv_sub_bytes(ciphertext);
+        }
+        *ciphertext ^= &self.round_keys[0];
+    }
+}
+
+/// The key is the tweakey `TK1 || ... || TKtk`, one 16-byte lane per row.
+impl SymmetricCipher<Matrix<u8>, Matrix<u8>> for DeoxysBC {
+    fn cipher(&self, key: &Matrix<u8>, plaintext: &mut Matrix<u8>) {
+        self.expand(key).encrypt_block(plaintext);
+    }
+
+    fn decipher(&self, key: &Matrix<u8>, plaintext: &mut Matrix<u8>) {
+        self.expand(key).decrypt_block(plaintext);
+    }
+}
+
+impl TweakableBlockCipher for DeoxysBC {
+    type Key = Matrix<u8>;
+    type Tweak = Matrix<u8>;
+    type Block = Matrix<u8>;
+
+    fn encrypt(&self, key: &Matrix<u8>, tweak: &Matrix<u8>, block: &mut Matrix<u8>) {
+        self.cipher(&tweakey(key, tweak), block);
+    }
+
+    fn decrypt(&self, key: &Matrix<u8>, tweak: &Matrix<u8>, block: &mut Matrix<u8>) {
+        self.decipher(&tweakey(key, tweak), block);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ciphers::aes::AES;
+    use crate::ciphers::deoxys::{DeoxysBC, H, RCON};
+    use crate::ciphers::TweakableBlockCipher;
+    use crate::matrix::Matrix;
+
+    fn bytes(seed: u8, n: usize) -> Vec<u8> {
+        (0..n).map(|i| (i as u8).wrapping_mul(seed).wrapping_add(0x5a)).collect()
+    }
+
+    #[test]
+    fn test_rcon() {
+        assert_eq!(RCON[..8], [0x2f, 0x5e, 0xbc, 0x63, 0xc6, 0x97, 0x35, 0x6a]);
+        assert_eq!(RCON[16], 0x72);
+    }
+
+    #[test]
+    fn test_h_order() {
+        let mut lane = (0..16).collect::<Vec<u8>>();
+        for round in 1..=8 {
+            lane = DeoxysBC::h(&lane);
+            assert_eq!(lane == (0..16).collect::<Vec<u8>>(), round == 8);
+        }
+        assert_eq!(DeoxysBC::h(&(0..16).collect::<Vec<u8>>()), H.map(|i| i as u8).to_vec());
+    }
+
+    #[test]
+    fn test_key_schedule() {
+        let deoxys = DeoxysBC::v256();
+        let stk = deoxys.key_schedule(&Matrix::new(2, 16, vec![0; 32]));
+        assert_eq!(stk.len(), 15);
+        let expected = [vec![1, 2, 4, 8], vec![RCON[3]; 4], vec![0; 8]].concat();
+        assert_eq!(AES::to_bytes(&stk[3]), expected);
+
+        // TK2 goes through LFSR2: 0x80 becomes 0x01 and is moved by h.
+        let mut key = vec![0; 32];
+        key[16] = 0x80;
+        let stk = deoxys.key_schedule(&Matrix::new(2, 16, key));
+        assert_eq!(AES::to_bytes(&stk[1])[7] ^ RCON[1], 0x01);
+    }
+
+    #[test]
+    fn test_tweakey_lfsrs() {
+        let deoxys = DeoxysBC::v384();
+        let (lfsr2, lfsr3) = (&deoxys.lfsrs[0], &deoxys.lfsrs[1]);
+        // LFSR2: (x7..x0) -> (x6..x0, x7 ^ x5), LFSR3: (x7..x0) -> (x0 ^ x6, x7..x1)
+        assert_eq!([0x01, 0x80, 0x20, 0x40].map(|it| lfsr2.eval(it)), [0x02, 0x01, 0x41, 0x80]);
+        assert_eq!([0x01, 0x80, 0x40, 0x02].map(|it| lfsr3.eval(it)), [0x80, 0x40, 0xa0, 0x01]);
+        assert!((0..256).all(|it| lfsr3.eval(lfsr2.eval(it)) == it));
+
+        // TK1 only goes through h, TK2 through LFSR2 and TK3 through LFSR3 before it.
+        for (z, expected) in [(0, 0x01), (1, 0x02), (2, 0x80)] {
+            let mut key = vec![0; 48];
+            key[16 * z] = 0x01;
+            let stk = deoxys.key_schedule(&Matrix::new(3, 16, key));
+            assert_eq!(AES::to_bytes(&stk[0])[0] ^ 1, 0x01);
+            assert_eq!(AES::to_bytes(&stk[1])[7] ^ RCON[1], expected);
+        }
+    }
+
+    #[test]
+    fn test_round_trip_deoxys_bc() {
+        for deoxys in [DeoxysBC::v256(), DeoxysBC::v384(), DeoxysBC::v384_with_rounds(9)] {
+            let key = Matrix::new(deoxys.tk - 1, 16, bytes(17, 16 * (deoxys.tk - 1)));
+            let tweak = Matrix::new(1, 16, bytes(29, 16));
+            let plaintext = AES::from_bytes(&bytes(3, 16));
+            let mut state = plaintext.clone();
+            deoxys.encrypt(&key, &tweak, &mut state);
+            assert_ne!(state, plaintext);
+            deoxys.decrypt(&key, &tweak, &mut state);
+            assert_eq!(state, plaintext);
+        }
+    }
+}
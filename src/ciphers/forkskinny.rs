@@ -0,0 +1,185 @@
+use std::ops::Range;
+use crate::ciphers::{Branch, ForkCipher};
+use crate::ciphers::skinny::SKINNY;
+use crate::matrix::Matrix;
+
+/// Round constants of ForkSkinny, produced by a 7-bit LFSR (SKINNY uses 6 bits).
+const RC: [u8; 87] = round_constants();
+
+const BC_64: [u8; 16] = [
+    0x1, 0x2, 0x4, 0x9, 0x3, 0x6, 0xd, 0xa, 0x5, 0xb, 0x7, 0xf, 0xe, 0xc, 0x8, 0x1
+];
+
+const BC_128: [u8; 16] = [
+    0x01, 0x02, 0x04, 0x08, 0x10, 0x20, 0x41, 0x82, 0x05, 0x0a, 0x14, 0x28, 0x51, 0xa2, 0x44, 0x88
+];
+
+const fn round_constants() -> [u8; 87] {
+    let mut rc = [0; 87];
+    let mut value = 0u8;
+    let mut i = 0;
+    while i < 87 {
+        value = ((value << 1) & 0x7F) | (((value >> 6) ^ (value >> 5) ^ 1) & 1);
+        rc[i] = value;
+        i += 1;
+    }
+    rc
+}
+
+/// ForkSkinny: `r_init` SKINNY rounds, then the state is forked. C1 is the output of the next
+/// `r1` rounds, C0 the output of the `r0` rounds after those on the state XORed with the
+/// branch constant. The tweakey schedule runs on through both branches.
+pub struct ForkSkinny {
+    skinny: SKINNY,
+    tk: usize,
+    bc: Matrix<u8>,
+    r_init: usize,
+    r0: usize,
+    r1: usize,
+}
+
+impl ForkSkinny {
+    pub fn v64_192() -> ForkSkinny {
+        Self::new(SKINNY::v64_with_rounds(17 + 23 + 23), 3, &BC_64, 17, 23, 23)
+    }
+
+    pub fn v128_256() -> ForkSkinny {
+        Self::new(SKINNY::v128_with_rounds(21 + 27 + 27), 2, &BC_128, 21, 27, 27)
+    }
+
+    pub fn v128_384() -> ForkSkinny {
+        Self::new(SKINNY::v128_with_rounds(25 + 31 + 31), 3, &BC_128, 25, 31, 31)
+    }
+
+    fn new(skinny: SKINNY, tk: usize, bc: &[u8; 16], r_init: usize, r0: usize, r1: usize) -> ForkSkinny {
+        assert!(r_init + r1 + r0 <= RC.len());
+        ForkSkinny { skinny, tk, bc: Matrix::new(4, 4, bc.to_vec()), r_init, r0, r1 }
+    }
+
+    #[inline]
+    fn c1_rounds(&self) -> Range<usize> {
+        self.r_init..self.r_init + self.r1
+    }
+
+    #[inline]
+    fn c0_rounds(&self) -> Range<usize> {
+        self.r_init + self.r1..self.r_init + self.r1 + self.r0
+    }
+
+    #[inline]
+    fn add_constants(internal_state: &mut Matrix<u8>, r: usize) {
+        let rc = RC[r];
+        internal_state[(0, 0)] ^= rc & 0xF;
+        internal_state[(1, 0)] ^= rc >> 4;
+        internal_state[(2, 0)] ^= 0x02;
+    }
+
+    fn round_tweak_keys(&self, key: &Matrix<u8>) -> Vec<Vec<Matrix<u8>>> {
+        assert_eq!(key.values.len(), 16 * self.tk);
+        self.skinny.key_schedule(key, self.tk)
+    }
+
+    fn forward(&self, internal_state: &mut Matrix<u8>, round_tweak_keys: &[Vec<Matrix<u8>>], rounds: Range<usize>) {
+        for round_num in rounds {
+            self.skinny.sub_cells(internal_state);
+            Self::add_constants(internal_state, round_num);
+            self.skinny.add_round_tweak_key(internal_state, &round_tweak_keys[round_num], self.tk);
+            self.skinny.shift_rows(internal_state);
+            self.skinny.mix_columns(internal_state);
+        }
+    }
+
+    fn backward(&self, internal_state: &mut Matrix<u8>, round_tweak_keys: &[Vec<Matrix<u8>>], rounds: Range<usize>) {
+        for round_num in rounds.rev() {
+            self.skinny.inv_mix_columns(internal_state);
+            self.skinny.inv_shift_rows(internal_state);
+            self.skinny.add_round_tweak_key(internal_state, &round_tweak_keys[round_num], self.tk);
+            Self::add_constants(internal_state, round_num);
+            self.skinny.inv_sub_cells(internal_state);
+        }
+    }
+
+    /// State at the forking point, computed back from the output block of `branch`.
+    fn fork_state(&self, ciphertext: &Matrix<u8>, round_tweak_keys: &[Vec<Matrix<u8>>], branch: Branch) -> Matrix<u8> {
+        let mut internal_state = ciphertext.clone();
+        match branch {
+            Branch::C0 => {
+                self.backward(&mut internal_state, round_tweak_keys, self.c0_rounds());
+                internal_state ^= &self.bc;
+            }
+            Branch::C1 => self.backward(&mut internal_state, round_tweak_keys, self.c1_rounds()),
+        }
+        internal_state
+    }
+}
+
+/// The key is the SKINNY tweakey state, one 16-cell lane per row.
+impl ForkCipher<Matrix<u8>, Matrix<u8>> for ForkSkinny {
+    fn encrypt(&self, key: &Matrix<u8>, plaintext: &Matrix<u8>) -> (Matrix<u8>, Matrix<u8>) {
+        let round_tweak_keys = self.round_tweak_keys(key);
+        let mut c1 = plaintext.clone();
+        self.forward(&mut c1, &round_tweak_keys, 0..self.r_init);
+        let mut c0 = &c1 ^ &self.bc;
+        self.forward(&mut c1, &round_tweak_keys, self.c1_rounds());
+        self.forward(&mut c0, &round_tweak_keys, self.c0_rounds());
+        (c0, c1)
+    }
+
+    fn invert(&self, key: &Matrix<u8>, ciphertext: &Matrix<u8>, branch: Branch) -> Matrix<u8> {
+        let round_tweak_keys = self.round_tweak_keys(key);
+        let mut plaintext = self.fork_state(ciphertext, &round_tweak_keys, branch);
+        self.backward(&mut plaintext, &round_tweak_keys, 0..self.r_init);
+        plaintext
+    }
+
+    fn reconstruct(&self, key: &Matrix<u8>, ciphertext: &Matrix<u8>, branch: Branch) -> Matrix<u8> {
+        let round_tweak_keys = self.round_tweak_keys(key);
+        let mut other = self.fork_state(ciphertext, &round_tweak_keys, branch);
+        match branch {
+            Branch::C0 => self.forward(&mut other, &round_tweak_keys, self.c1_rounds()),
+            Branch::C1 => {
+                other ^= &self.bc;
+                self.forward(&mut other, &round_tweak_keys, self.c0_rounds());
+            }
+        }
+        other
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ciphers::forkskinny::{ForkSkinny, RC};
+    use crate::ciphers::{Branch, ForkCipher};
+    use crate::matrix::Matrix;
+
+    fn check_fork_operations(cipher: ForkSkinny, mask: u8) {
+        let key = Matrix::new(cipher.tk, 16, (0..16 * cipher.tk).map(|i| (i as u8).wrapping_mul(37) & mask).collect());
+        let plaintext = Matrix::new(4, 4, (0..16).map(|i| (i as u8).wrapping_mul(113).wrapping_add(5) & mask).collect());
+        let (c0, c1) = cipher.encrypt(&key, &plaintext);
+        assert_ne!(c0, c1);
+        assert_eq!(cipher.invert(&key, &c0, Branch::C0), plaintext);
+        assert_eq!(cipher.invert(&key, &c1, Branch::C1), plaintext);
+        assert_eq!(cipher.reconstruct(&key, &c0, Branch::C0), c1);
+        assert_eq!(cipher.reconstruct(&key, &c1, Branch::C1), c0);
+    }
+
+    #[test]
+    fn test_fork_operations_forkskinny_64_192() {
+        check_fork_operations(ForkSkinny::v64_192(), 0xF);
+    }
+
+    #[test]
+    fn test_fork_operations_forkskinny_128_256() {
+        check_fork_operations(ForkSkinny::v128_256(), 0xFF);
+    }
+
+    #[test]
+    fn test_fork_operations_forkskinny_128_384() {
+        check_fork_operations(ForkSkinny::v128_384(), 0xFF);
+    }
+
+    #[test]
+    fn test_round_constants() {
+        assert_eq!(RC[..12], [0x01, 0x03, 0x07, 0x0f, 0x1f, 0x3f, 0x7e, 0x7d, 0x7b, 0x77, 0x6f, 0x5f]);
+    }
+}
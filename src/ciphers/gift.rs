@@ -0,0 +1,239 @@
+use crate::ciphers::{ExpandedBlockCipher, SymmetricCipher};
+
+// Data representation
+// The state is an integer, bit b_0 being the least significant one and nibble i bits 4i..4i+3.
+// The key is the 128-bit integer k7 || ... || k0 of 16-bit words, k0 being the least significant.
+
+pub(crate) const GIFT_SBOX: [u8; 16] = [
+    0x1, 0xa, 0x4, 0xc, 0x6, 0xf, 0x3, 0x9, 0x2, 0xd, 0xb, 0x7, 0x5, 0x0, 0x8, 0xe
+];
+
+const INV_GIFT_SBOX: [u8; 16] = [
+    0xd, 0x0, 0x8, 0x6, 0x2, 0xc, 0x4, 0xb, 0xe, 0x7, 0x1, 0xa, 0x3, 0x9, 0xf, 0x5
+];
+
+/// Produced by the 6-bit LFSR of SKINNY, one constant per round.
+const RC: [u8; 48] = [
+    0x01, 0x03, 0x07, 0x0F, 0x1F, 0x3E, 0x3D, 0x3B, 0x37, 0x2F, 0x1E, 0x3C, 0x39, 0x33, 0x27, 0x0E,
+    0x1D, 0x3A, 0x35, 0x2B, 0x16, 0x2C, 0x18, 0x30, 0x21, 0x02, 0x05, 0x0B, 0x17, 0x2E, 0x1C, 0x38,
+    0x31, 0x23, 0x06, 0x0D, 0x1B, 0x36, 0x2D, 0x1A, 0x34, 0x29, 0x12, 0x24, 0x08, 0x11, 0x22, 0x04
+];
+
+const GIFT_64_P: [usize; 64] = permutation();
+
+const GIFT_128_P: [usize; 128] = permutation();
+
+/// Bit `i` of the state moves to bit `P[i]`.
+const fn permutation<const N: usize>() -> [usize; N] {
+    let mut p = [0; N];
+    let mut i = 0;
+    while i < N {
+        p[i] = 4 * (i / 16) + (N / 4) * ((3 * ((i % 16) / 4) + (i % 4)) % 4) + (i % 4);
+        i += 1;
+    }
+    p
+}
+
+#[inline]
+fn sub_cells(state: u128, n: usize, sbox: &[u8; 16]) -> u128 {
+    (0..n / 4).fold(0, |acc, i| acc | (sbox[(state >> (4 * i)) as usize & 0xF] as u128) << (4 * i))
+}
+
+#[inline]
+fn perm_bits(state: u128, p: &[usize]) -> u128 {
+    p.iter()
+        .enumerate()
+        .fold(0, |acc, (i, &to)| acc | (state >> i & 1) << to)
+}
+
+#[inline]
+fn inv_perm_bits(state: u128, p: &[usize]) -> u128 {
+    p.iter()
+        .enumerate()
+        .fold(0, |acc, (i, &from)| acc | (state >> from & 1) << i)
+}
+
+/// `(k1 >>> 2) || (k0 >>> 12) || k7 || ... || k2`.
+#[inline]
+fn update_key(key: u128) -> u128 {
+    let k0 = (key as u16).rotate_right(12) as u128;
+    let k1 = ((key >> 16) as u16).rotate_right(2) as u128;
+    key >> 32 | k0 << 96 | k1 << 112
+}
+
+/// Round keys with the constants already added, `n` being the block size.
+fn round_keys(key: u128, n: usize, r: usize) -> Vec<u128> {
+    let mut key = key;
+    (0..r).map(|round_num| {
+        let (u, v, shift) = if n == 64 {
+            (key >> 16 & 0xFFFF, key & 0xFFFF, 0)
+        } else {
+            (key >> 64 & 0xFFFF_FFFF, key & 0xFFFF_FFFF, 1)
+        };
+        let mut round_key = (0..n / 4).fold(0, |acc, i| {
+            acc | (u >> i & 1) << (4 * i + 1 + shift) | (v >> i & 1) << (4 * i + shift)
+        });
+        round_key |= 1 << (n - 1);
+        round_key |= (0..6).fold(0, |acc, i| acc | (RC[round_num] as u128 >> i & 1) << (4 * i + 3));
+        key = update_key(key);
+        round_key
+    }).collect()
+}
+
+/// Round keys of a GIFT instance, processing blocks of type `B` (`u64` for GIFT-64, `u128` for GIFT-128).
+pub struct ExpandedKey<B> {
+    round_keys: Vec<B>,
+}
+
+impl ExpandedBlockCipher<u64> for ExpandedKey<u64> {
+    fn encrypt_block(&self, plaintext: &mut u64) {
+        for round_key in &self.round_keys {
+            let state = sub_cells(*plaintext as u128, 64, &GIFT_SBOX);
+            *plaintext = perm_bits(state, &GIFT_64_P) as u64 ^ round_key;
+        }
+    }
+
+    fn decrypt_block(&self, ciphertext: &mut u64) {
+        for round_key in self.round_keys.iter().rev() {
+            let state = inv_perm_bits((*ciphertext ^ round_key) as u128, &GIFT_64_P);
+            *ciphertext = sub_cells(state, 64, &INV_GIFT_SBOX) as u64;
+        }
+    }
+}
+
+impl ExpandedBlockCipher<u128> for ExpandedKey<u128> {
+    fn encrypt_block(&self, plaintext: &mut u128) {
+        for round_key in &self.round_keys {
+            let state = sub_cells(*plaintext, 128, &GIFT_SBOX);
+            *plaintext = perm_bits(state, &GIFT_128_P) ^ round_key;
+        }
+    }
+
+    fn decrypt_block(&self, ciphertext: &mut u128) {
+        for round_key in self.round_keys.iter().rev() {
+            let state = inv_perm_bits(*ciphertext ^ round_key, &GIFT_128_P);
+            *ciphertext = sub_cells(state, 128, &INV_GIFT_SBOX);
+        }
+    }
+}
+
+/// GIFT-64 with a 128-bit key, 28 rounds by default.
+pub struct GIFT64 {
+    r: usize,
+}
+
+impl Default for GIFT64 {
+    fn default() -> GIFT64 {
+        GIFT64::with_rounds(28)
+    }
+}
+
+impl GIFT64 {
+    pub fn with_rounds(r: usize) -> GIFT64 {
+        assert!(r <= RC.len());
+        GIFT64 { r }
+    }
+
+    pub fn expand(&self, key: u128) -> ExpandedKey<u64> {
+        let round_keys = round_keys(key, 64, self.r)
+            .into_iter()
+            .map(|round_key| round_key as u64)
+            .collect();
+        ExpandedKey { round_keys }
+    }
+}
+
+impl SymmetricCipher<u128, u64> for GIFT64 {
+    fn cipher(&self, key: &u128, plaintext: &mut u64) {
+        self.expand(*key).encrypt_block(plaintext);
+    }
+
+    fn decipher(&self, key: &u128, plaintext: &mut u64) {
+        self.expand(*key).decrypt_block(plaintext);
+    }
+}
+
+/// GIFT-128 with a 128-bit key, 40 rounds by default.
+pub struct GIFT128 {
+    r: usize,
+}
+
+impl Default for GIFT128 {
+    fn default() -> GIFT128 {
+        GIFT128::with_rounds(40)
+    }
+}
+
+impl GIFT128 {
+    pub fn with_rounds(r: usize) -> GIFT128 {
+        assert!(r <= RC.len());
+        GIFT128 { r }
+    }
+
+    pub fn expand(&self, key: u128) -> ExpandedKey<u128> {
+        ExpandedKey { round_keys: round_keys(key, 128, self.r) }
+    }
+}
+
+impl SymmetricCipher<u128, u128> for GIFT128 {
+    fn cipher(&self, key: &u128, plaintext: &mut u128) {
+        self.expand(*key).encrypt_block(plaintext);
+    }
+
+    fn decipher(&self, key: &u128, plaintext: &mut u128) {
+        self.expand(*key).decrypt_block(plaintext);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ciphers::gift::{GIFT128, GIFT64, GIFT_SBOX, INV_GIFT_SBOX};
+    use crate::ciphers::SymmetricCipher;
+
+    fn check_gift_64(key: u128, plaintext: u64, ciphertext: u64) {
+        let gift = GIFT64::default();
+        let mut state = plaintext;
+        gift.cipher(&key, &mut state);
+        assert_eq!(state, ciphertext);
+        gift.decipher(&key, &mut state);
+        assert_eq!(state, plaintext);
+    }
+
+    fn check_gift_128(key: u128, plaintext: u128, ciphertext: u128) {
+        let gift = GIFT128::default();
+        let mut state = plaintext;
+        gift.cipher(&key, &mut state);
+        assert_eq!(state, ciphertext);
+        gift.decipher(&key, &mut state);
+        assert_eq!(state, plaintext);
+    }
+
+    #[test]
+    fn test_vectors_gift_64() {
+        check_gift_64(0, 0, 0xf62bc3ef34f775ac);
+        check_gift_64(0xfedcba9876543210fedcba9876543210, 0xfedcba9876543210, 0xc1b71f66160ff587);
+        check_gift_64(0xbd91731eb6bc2713a1f9f6ffc75044e7, 0xc450c7727a9b8a7d, 0xe3272885fa94ba8b);
+    }
+
+    #[test]
+    fn test_vectors_gift_128() {
+        check_gift_128(0, 0, 0xcd0bd738388ad3f668b15a36ceb6ff92);
+        check_gift_128(
+            0xfedcba9876543210fedcba9876543210,
+            0xfedcba9876543210fedcba9876543210,
+            0x8422241a6dbf5a9346af468409ee0152,
+        );
+        check_gift_128(
+            0xd0f5c59a7700d3e799028fa9f90ad837,
+            0xe39c141fa57dba43f08a85b6a91f86c1,
+            0x13ede67cbdcc3dbf400a62d6977265ea,
+        );
+    }
+
+    #[test]
+    fn test_inv_sbox() {
+        for i in 0..16 {
+            assert_eq!(INV_GIFT_SBOX[GIFT_SBOX[i] as usize] as usize, i);
+        }
+    }
+}
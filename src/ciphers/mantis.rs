@@ -0,0 +1,209 @@
+use crate::ciphers::{SymmetricCipher, TweakableBlockCipher, tweakey};
+use crate::matrix::Matrix;
+
+// MANTIS uses the involutive S-box Sb0 of Midori, not the SKINNY-64 one.
+pub(crate) const MIDORI_SBOX: [u8; 16] = [
+    0xc, 0xa, 0xd, 0x3, 0xe, 0xb, 0xf, 0x7, 0x8, 0x9, 0x1, 0x5, 0x0, 0x2, 0x4, 0x6
+];
+
+const RC: [u64; 8] = [
+    0x13198a2e03707344, 0xa4093822299f31d0, 0x082efa98ec4e6c89, 0x452821e638d01377,
+    0xbe5466cf34e90c6c, 0xc0ac29b7c97c50dd, 0x3f84d5b5b5470917, 0x9216d5d98979fb1b,
+];
+
+const ALPHA: u64 = 0x243f6a8885a308d3;
+
+const P: [usize; 16] = [
+    0, 11, 6, 13, 10, 1, 12, 7, 5, 14, 3, 8, 15, 4, 9, 2
+];
+
+const INV_P: [usize; 16] = [
+    0, 5, 15, 10, 13, 8, 2, 7, 11, 14, 4, 1, 6, 3, 9, 12
+];
+
+const H: [usize; 16] = [
+    6, 5, 14, 15, 0, 1, 2, 3, 7, 12, 13, 4, 8, 9, 10, 11
+];
+
+const INV_H: [usize; 16] = [
+    4, 5, 6, 7, 11, 1, 0, 8, 12, 13, 14, 15, 9, 10, 2, 3
+];
+
+/// MANTIS_r, r in 1..=8 (MANTIS-5 to MANTIS-8 are the proposed instances): r forward rounds,
+/// a middle layer and r backward rounds, the backward half using the tweakey `k1 ^ alpha`.
+pub struct MANTIS {
+    r: usize,
+}
+
+impl MANTIS {
+    pub fn with_rounds(rounds: usize) -> MANTIS {
+        assert!((1..=RC.len()).contains(&rounds));
+        MANTIS { r: rounds }
+    }
+
+    #[inline]
+    fn to_u64(nibbles: &[u8]) -> u64 {
+        assert_eq!(nibbles.len(), 16);
+        nibbles.iter().fold(0, |acc, nibble| acc << 4 | (*nibble as u64 & 0xF))
+    }
+
+    #[inline]
+    fn from_u64(value: u64) -> Matrix<u8> {
+        Matrix::new(4, 4, (0..16).map(|i| (value >> (60 - 4 * i) & 0xF) as u8).collect())
+    }
+
+    /// Splits the tweakey `T || k0 || k1` into `(T, k0, k1)`.
+    fn split_key(key: &Matrix<u8>) -> (Matrix<u8>, u64, u64) {
+        assert_eq!(key.values.len(), 48);
+        let tweak = Matrix::new(4, 4, key.values[..16].to_vec());
+        (tweak, Self::to_u64(&key.values[16..32]), Self::to_u64(&key.values[32..]))
+    }
+
+    #[inline]
+    fn sub_cells(internal_state: &mut Matrix<u8>) {
+        internal_state.iter_mut()
+            .for_each(|it| *it = MIDORI_SBOX[*it as usize])
+    }
+
+    #[inline]
+    fn permute(internal_state: &Matrix<u8>, permutation: &[usize; 16]) -> Matrix<u8> {
+        Matrix::new(4, 4, (0..16).map(|i| internal_state.values[permutation[i]]).collect())
+    }
+
+    #[inline]
+    fn mix_columns(internal_state: &mut Matrix<u8>) {
+        for j in 0..4 {
+            let sum = (0..4).fold(0, |acc, i| acc ^ internal_state[(i, j)]);
+            for i in 0..4 {
+                internal_state[(i, j)] ^= sum;
+            }
+        }
+    }
+
+    /// Encryption with `(k0, k0', k1)`; decryption is the same function with `(k0', k0, k1 ^ alpha)`.
+    fn core(&self, k0: u64, k0_prime: u64, k1: u64, tweak: &Matrix<u8>, internal_state: &mut Matrix<u8>) {
+        let mut tweak = tweak.clone();
+        *internal_state ^= &Self::from_u64(k0 ^ k1) ^ &tweak;
+        let k1 = Self::from_u64(k1);
+        for &rc in RC.iter().take(self.r) {
+            tweak = Self::permute(&tweak, &H);
+            Self::sub_cells(internal_state);
+            *internal_state ^= Self::from_u64(rc);
+            *internal_state ^= &k1 ^ &tweak;
+            *internal_state = Self::permute(internal_state, &P);
+            Self::mix_columns(internal_state);
+        }
+        Self::sub_cells(internal_state);
+        Self::mix_columns(internal_state);
+        Self::sub_cells(internal_state);
+        let k1_alpha = &k1 ^ &Self::from_u64(ALPHA);
+        for round_num in (0..self.r).rev() {
+            Self::mix_columns(internal_state);
+            *internal_state = Self::permute(internal_state, &INV_P);
+            *internal_state ^= &k1_alpha ^ &tweak;
+            *internal_state ^= Self::from_u64(RC[round_num]);
+            Self::sub_cells(internal_state);
+            tweak = Self::permute(&tweak, &INV_H);
+        }
+        *internal_state ^= Self::from_u64(k0_prime) ^ k1_alpha ^ tweak;
+    }
+
+    #[inline]
+    fn k0_prime(k0: u64) -> u64 {
+        k0.rotate_right(1) ^ (k0 >> 63)
+    }
+}
+
+/// The key is the tweakey `T || k0 || k1`, as a 3x16 matrix.
+impl SymmetricCipher<Matrix<u8>, Matrix<u8>> for MANTIS {
+    fn cipher(&self, key: &Matrix<u8>, plaintext: &mut Matrix<u8>) {
+        let (tweak, k0, k1) = Self::split_key(key);
+        self.core(k0, Self::k0_prime(k0), k1, &tweak, plaintext);
+    }
+
+    fn decipher(&self, key: &Matrix<u8>, plaintext: &mut Matrix<u8>) {
+        let (tweak, k0, k1) = Self::split_key(key);
+        self.core(Self::k0_prime(k0), k0, k1 ^ ALPHA, &tweak, plaintext);
+    }
+}
+
+impl TweakableBlockCipher for MANTIS {
+    type Key = Matrix<u8>;
+    type Tweak = Matrix<u8>;
+    type Block = Matrix<u8>;
+
+    fn encrypt(&self, key: &Matrix<u8>, tweak: &Matrix<u8>, block: &mut Matrix<u8>) {
+        self.cipher(&tweakey(key, tweak), block);
+    }
+
+    fn decrypt(&self, key: &Matrix<u8>, tweak: &Matrix<u8>, block: &mut Matrix<u8>) {
+        self.decipher(&tweakey(key, tweak), block);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ciphers::mantis::{H, INV_H, INV_P, MANTIS, P};
+    use crate::ciphers::{SymmetricCipher, TweakableBlockCipher};
+    use crate::matrix::Matrix;
+
+    fn parse_nibbles(word: &'static str) -> Vec<u8> {
+        fn parse_digit(c: char) -> u8 {
+            c.to_digit(16).unwrap() as u8
+        }
+
+        word.chars().map(parse_digit).collect()
+    }
+
+    fn check_test_vector(rounds: usize, plaintext: &'static str, ciphertext: &'static str) {
+        let mantis = MANTIS::with_rounds(rounds);
+        let key = Matrix::new(3, 16, parse_nibbles("ba912e6f1055fed292f09952c625e3e9d7a060f714c0292b"));
+        let plaintext = Matrix::new(4, 4, parse_nibbles(plaintext));
+        let ciphertext = Matrix::new(4, 4, parse_nibbles(ciphertext));
+        let mut state = plaintext.clone();
+        mantis.cipher(&key, &mut state);
+        assert_eq!(state, ciphertext);
+        mantis.decipher(&key, &mut state);
+        assert_eq!(state, plaintext);
+    }
+
+    #[test]
+    fn test_vector_mantis_5() {
+        check_test_vector(5, "3b5c77a4921f9718", "d6522035c1c0c6c1");
+    }
+
+    #[test]
+    fn test_vector_mantis_6() {
+        check_test_vector(6, "d6522035c1c0c6c1", "60e43457311936fd");
+    }
+
+    #[test]
+    fn test_vector_mantis_7() {
+        check_test_vector(7, "60e43457311936fd", "308e8a07f168f517");
+    }
+
+    #[test]
+    fn test_vector_mantis_8() {
+        check_test_vector(8, "308e8a07f168f517", "971ea01a86b410bb");
+    }
+
+    #[test]
+    fn test_inv_permutations() {
+        for i in 0..16 {
+            assert_eq!(P[INV_P[i]], i);
+            assert_eq!(H[INV_H[i]], i);
+        }
+    }
+
+    #[test]
+    fn test_tweakable_mantis() {
+        let mantis = MANTIS::with_rounds(7);
+        let key = Matrix::new(2, 16, parse_nibbles("92f09952c625e3e9d7a060f714c0292b"));
+        let tweak = Matrix::new(1, 16, parse_nibbles("ba912e6f1055fed2"));
+        let mut state = Matrix::new(4, 4, parse_nibbles("60e43457311936fd"));
+        mantis.encrypt(&key, &tweak, &mut state);
+        assert_eq!(state, Matrix::new(4, 4, parse_nibbles("308e8a07f168f517")));
+        mantis.decrypt(&key, &tweak, &mut state);
+        assert_eq!(state, Matrix::new(4, 4, parse_nibbles("60e43457311936fd")));
+    }
+}
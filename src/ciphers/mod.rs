@@ -1,9 +1,71 @@
+pub mod aes;
+pub mod bitsliced;
+pub mod craft;
+pub mod deoxys;
+pub mod forkskinny;
+pub mod gift;
+pub mod mantis;
+pub mod packed;
 pub mod skinny;
 pub mod skinnye_v2;
 pub mod skinnyee;
 
+use crate::matrix::Matrix;
+
 pub trait SymmetricCipher<K, T> {
     fn cipher(&self, key: &K, plaintext: &mut T);
     fn decipher(&self, key: &K, plaintext: &mut T);
 }
 
+/// Cipher instance with its round keys already expanded, processing blocks of type `B`.
+pub trait ExpandedBlockCipher<B> {
+    fn encrypt_block(&self, plaintext: &mut B);
+    fn decrypt_block(&self, ciphertext: &mut B);
+}
+
+/// Expanded cipher whose rounds can also be run one at a time, so that the state can be observed
+/// right after the S-box layer of each round.
+pub trait RoundByRoundBlockCipher<B>: ExpandedBlockCipher<B> {
+    fn nb_rounds(&self) -> usize;
+    /// S-box layer of round `round`.
+    fn sub_cells(&self, round: usize, state: &mut B);
+    /// Rest of round `round`, from the round key addition on.
+    fn finish_round(&self, round: usize, state: &mut B);
+}
+
+/// Block cipher whose secret key and public tweak are passed separately.
+pub trait TweakableBlockCipher {
+    type Key;
+    type Tweak;
+    type Block;
+
+    fn encrypt(&self, key: &Self::Key, tweak: &Self::Tweak, block: &mut Self::Block);
+    fn decrypt(&self, key: &Self::Key, tweak: &Self::Tweak, block: &mut Self::Block);
+}
+
+/// Output branch of a forkcipher.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Branch {
+    C0,
+    C1,
+}
+
+/// Forkcipher: one input block is expanded into two output blocks, C0 and C1.
+pub trait ForkCipher<K, T> {
+    /// Returns `(C0, C1)`.
+    fn encrypt(&self, key: &K, plaintext: &T) -> (T, T);
+    /// Recovers the plaintext from the output block of `branch`.
+    fn invert(&self, key: &K, ciphertext: &T, branch: Branch) -> T;
+    /// Computes the other output block from the output block of `branch`.
+    fn reconstruct(&self, key: &K, ciphertext: &T, branch: Branch) -> T;
+}
+
+/// TWEAKEY state of the SKINNY family: the tweak lanes come first (TK1, ...) followed by the key lanes.
+fn tweakey(key: &Matrix<u8>, tweak: &Matrix<u8>) -> Matrix<u8> {
+    let values = tweak.values.iter()
+        .chain(key.values.iter())
+        .cloned()
+        .collect::<Vec<_>>();
+    assert_eq!(values.len() % 16, 0);
+    Matrix::new(values.len() / 16, 16, values)
+}
@@ -0,0 +1,153 @@
+use crate::ciphers::ExpandedBlockCipher;
+use crate::matrix::Matrix;
+
+// Data representation
+// The 4x4 nibble state is stored in a u64, cell (row, col) at bits 4 * (4 * row + col).
+// Row i is thus the 16-bit word at bits 16 * i, with column j at bits 4 * j of this word.
+
+const SKINNY_64_SBOX: [u8; 16] = [
+    12, 6, 9, 0, 1, 10, 2, 11, 3, 8, 5, 13, 4, 14, 7, 15
+];
+
+const INV_SKINNY_64_SBOX: [u8; 16] = [
+    3, 4, 6, 8, 12, 10, 1, 14, 9, 2, 5, 7, 0, 11, 13, 15
+];
+
+const SKINNY_64_SBOX_8: [u8; 256] = sbox_8(&SKINNY_64_SBOX);
+
+const INV_SKINNY_64_SBOX_8: [u8; 256] = sbox_8(&INV_SKINNY_64_SBOX);
+
+/// Applies a 4-bit S-box to both nibbles of a byte at once.
+const fn sbox_8(sbox: &[u8; 16]) -> [u8; 256] {
+    let mut table = [0; 256];
+    let mut i = 0;
+    while i < 256 {
+        table[i] = (sbox[i >> 4] << 4) | sbox[i & 0xF];
+        i += 1;
+    }
+    table
+}
+
+pub fn pack(state: &Matrix<u8>) -> u64 {
+    assert_eq!(state.values.len(), 16);
+    state.iter()
+        .enumerate()
+        .fold(0, |acc, (i, nibble)| acc | ((*nibble as u64 & 0xF) << (4 * i)))
+}
+
+pub fn unpack(state: u64) -> Matrix<u8> {
+    Matrix::new(4, 4, (0..16).map(|i| ((state >> (4 * i)) & 0xF) as u8).collect())
+}
+
+#[inline]
+fn substitute(state: u64, table: &[u8; 256]) -> u64 {
+    let mut bytes = state.to_le_bytes();
+    bytes.iter_mut().for_each(|it| *it = table[*it as usize]);
+    u64::from_le_bytes(bytes)
+}
+
+#[inline]
+fn rows(state: u64) -> [u16; 4] {
+    [state as u16, (state >> 16) as u16, (state >> 32) as u16, (state >> 48) as u16]
+}
+
+#[inline]
+fn from_rows(rows: [u16; 4]) -> u64 {
+    rows[0] as u64 | (rows[1] as u64) << 16 | (rows[2] as u64) << 32 | (rows[3] as u64) << 48
+}
+
+#[inline]
+pub fn sub_cells(state: u64) -> u64 {
+    substitute(state, &SKINNY_64_SBOX_8)
+}
+
+#[inline]
+pub fn inv_sub_cells(state: u64) -> u64 {
+    substitute(state, &INV_SKINNY_64_SBOX_8)
+}
+
+#[inline]
+pub fn shift_rows(state: u64) -> u64 {
+    let [r0, r1, r2, r3] = rows(state);
+    from_rows([r0, r1.rotate_left(4), r2.rotate_left(8), r3.rotate_left(12)])
+}
+
+#[inline]
+pub fn inv_shift_rows(state: u64) -> u64 {
+    let [r0, r1, r2, r3] = rows(state);
+    from_rows([r0, r1.rotate_right(4), r2.rotate_right(8), r3.rotate_right(12)])
+}
+
+#[inline]
+pub fn mix_columns(state: u64) -> u64 {
+    let [r0, mut r1, mut r2, mut r3] = rows(state);
+    r1 ^= r2;
+    r2 ^= r0;
+    r3 ^= r2;
+    from_rows([r3, r0, r1, r2])
+}
+
+#[inline]
+pub fn inv_mix_columns(state: u64) -> u64 {
+    let [mut r0, r1, mut r2, mut r3] = rows(state);
+    r0 ^= r3;
+    r3 ^= r1;
+    r2 ^= r3;
+    from_rows([r1, r2, r3, r0])
+}
+
+/// Round keys of a 64-bit SKINNY-like cipher, with the round constants already folded in.
+pub struct PackedRoundKeys {
+    round_keys: Vec<u64>,
+}
+
+impl PackedRoundKeys {
+    pub fn new(round_keys: &[Matrix<u8>]) -> PackedRoundKeys {
+        PackedRoundKeys {
+            round_keys: round_keys.iter().map(pack).collect(),
+        }
+    }
+
+    pub fn round_keys(&self) -> &[u64] {
+        &self.round_keys
+    }
+}
+
+impl ExpandedBlockCipher<u64> for PackedRoundKeys {
+    fn encrypt_block(&self, plaintext: &mut u64) {
+        let mut state = *plaintext;
+        for round_key in &self.round_keys {
+            state = mix_columns(shift_rows(sub_cells(state) ^ round_key));
+        }
+        *plaintext = state;
+    }
+
+    fn decrypt_block(&self, ciphertext: &mut u64) {
+        let mut state = *ciphertext;
+        for round_key in self.round_keys.iter().rev() {
+            state = inv_sub_cells(inv_shift_rows(inv_mix_columns(state)) ^ round_key);
+        }
+        *ciphertext = state;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ciphers::packed::{inv_mix_columns, inv_shift_rows, inv_sub_cells, mix_columns, pack, shift_rows, sub_cells, unpack};
+    use crate::matrix::Matrix;
+
+    #[test]
+    fn test_pack_unpack() {
+        let state = Matrix::new(4, 4, (0..16).collect());
+        assert_eq!(pack(&state), 0xfedcba9876543210);
+        assert_eq!(unpack(pack(&state)), state);
+    }
+
+    #[test]
+    fn test_inverse_operations() {
+        let state = 0x06034f957724d19d;
+        assert_eq!(inv_sub_cells(sub_cells(state)), state);
+        assert_eq!(inv_shift_rows(shift_rows(state)), state);
+        assert_eq!(inv_mix_columns(mix_columns(state)), state);
+    }
+}
@@ -1,14 +1,20 @@
 use std::mem::swap;
+use std::ops::Range;
 use std::vec;
-use crate::ciphers::SymmetricCipher;
+use crate::ciphers::{ExpandedBlockCipher, RoundByRoundBlockCipher, SymmetricCipher, TweakableBlockCipher, tweakey};
+use crate::ciphers::packed::PackedRoundKeys;
 use crate::lfsr::{LFSR, x};
 use crate::matrix::Matrix;
 
-const SKINNY_64_SBOX: [u8; 16] = [
+pub(crate) const SKINNY_64_SBOX: [u8; 16] = [
     12, 6, 9, 0, 1, 10, 2, 11, 3, 8, 5, 13, 4, 14, 7, 15
 ];
 
-const SKINNY_128_SBOX: [u8; 256] = [
+const INV_SKINNY_64_SBOX: [u8; 16] = [
+    3, 4, 6, 8, 12, 10, 1, 14, 9, 2, 5, 7, 0, 11, 13, 15
+];
+
+pub(crate) const SKINNY_128_SBOX: [u8; 256] = [
     0x65, 0x4c, 0x6a, 0x42, 0x4b, 0x63, 0x43, 0x6b, 0x55, 0x75, 0x5a, 0x7a, 0x53, 0x73, 0x5b, 0x7b,
     0x35, 0x8c, 0x3a, 0x81, 0x89, 0x33, 0x80, 0x3b, 0x95, 0x25, 0x98, 0x2a, 0x90, 0x23, 0x99, 0x2b,
     0xe5, 0xcc, 0xe8, 0xc1, 0xc9, 0xe0, 0xc0, 0xe9, 0xd5, 0xf5, 0xd8, 0xf8, 0xd0, 0xf0, 0xd9, 0xf9,
@@ -27,6 +33,25 @@ const SKINNY_128_SBOX: [u8; 256] = [
     0xe2, 0xca, 0xee, 0xc6, 0xcf, 0xe7, 0xc7, 0xef, 0xd2, 0xf2, 0xde, 0xfe, 0xd7, 0xf7, 0xdf, 0xff
 ];
 
+const INV_SKINNY_128_SBOX: [u8; 256] = [
+    0xac, 0xe8, 0x68, 0x3c, 0x6c, 0x38, 0xa8, 0xec, 0xaa, 0xae, 0x3a, 0x3e, 0x6a, 0x6e, 0xea, 0xee,
+    0xa6, 0xa3, 0x33, 0x36, 0x66, 0x63, 0xe3, 0xe6, 0xe1, 0xa4, 0x61, 0x34, 0x31, 0x64, 0xa1, 0xe4,
+    0x8d, 0xc9, 0x49, 0x1d, 0x4d, 0x19, 0x89, 0xcd, 0x8b, 0x8f, 0x1b, 0x1f, 0x4b, 0x4f, 0xcb, 0xcf,
+    0x85, 0xc0, 0x40, 0x15, 0x45, 0x10, 0x80, 0xc5, 0x82, 0x87, 0x12, 0x17, 0x42, 0x47, 0xc2, 0xc7,
+    0x96, 0x93, 0x03, 0x06, 0x56, 0x53, 0xd3, 0xd6, 0xd1, 0x94, 0x51, 0x04, 0x01, 0x54, 0x91, 0xd4,
+    0x9c, 0xd8, 0x58, 0x0c, 0x5c, 0x08, 0x98, 0xdc, 0x9a, 0x9e, 0x0a, 0x0e, 0x5a, 0x5e, 0xda, 0xde,
+    0x95, 0xd0, 0x50, 0x05, 0x55, 0x00, 0x90, 0xd5, 0x92, 0x97, 0x02, 0x07, 0x52, 0x57, 0xd2, 0xd7,
+    0x9d, 0xd9, 0x59, 0x0d, 0x5d, 0x09, 0x99, 0xdd, 0x9b, 0x9f, 0x0b, 0x0f, 0x5b, 0x5f, 0xdb, 0xdf,
+    0x16, 0x13, 0x83, 0x86, 0x46, 0x43, 0xc3, 0xc6, 0x41, 0x14, 0xc1, 0x84, 0x11, 0x44, 0x81, 0xc4,
+    0x1c, 0x48, 0xc8, 0x8c, 0x4c, 0x18, 0x88, 0xcc, 0x1a, 0x1e, 0x8a, 0x8e, 0x4a, 0x4e, 0xca, 0xce,
+    0x35, 0x60, 0xe0, 0xa5, 0x65, 0x30, 0xa0, 0xe5, 0x32, 0x37, 0xa2, 0xa7, 0x62, 0x67, 0xe2, 0xe7,
+    0x3d, 0x69, 0xe9, 0xad, 0x6d, 0x39, 0xa9, 0xed, 0x3b, 0x3f, 0xab, 0xaf, 0x6b, 0x6f, 0xeb, 0xef,
+    0x26, 0x23, 0xb3, 0xb6, 0x76, 0x73, 0xf3, 0xf6, 0x71, 0x24, 0xf1, 0xb4, 0x21, 0x74, 0xb1, 0xf4,
+    0x2c, 0x78, 0xf8, 0xbc, 0x7c, 0x28, 0xb8, 0xfc, 0x2a, 0x2e, 0xba, 0xbe, 0x7a, 0x7e, 0xfa, 0xfe,
+    0x25, 0x70, 0xf0, 0xb5, 0x75, 0x20, 0xb0, 0xf5, 0x22, 0x27, 0xb2, 0xb7, 0x72, 0x77, 0xf2, 0xf7,
+    0x2d, 0x79, 0xf9, 0xbd, 0x7d, 0x29, 0xb9, 0xfd, 0x2b, 0x2f, 0xbb, 0xbf, 0x7b, 0x7f, 0xfb, 0xff
+];
+
 const RC: [u8; 62] = [
     0x01, 0x03, 0x07, 0x0F, 0x1F, 0x3E, 0x3D, 0x3B, 0x37, 0x2F, 0x1E, 0x3C, 0x39, 0x33,
     0x27, 0x0E, 0x1D, 0x3A, 0x35, 0x2B, 0x16, 0x2C, 0x18, 0x30, 0x21, 0x02, 0x05, 0x0B,
@@ -45,55 +70,101 @@ const PT: [usize; 16] = [
     9, 15, 8, 13, 10, 14, 12, 11, 0, 1, 2, 3, 4, 5, 6, 7
 ];
 
-#[allow(dead_code)]
+const INV_PT: [usize; 16] = [
+    8, 9, 10, 11, 12, 13, 14, 15, 2, 0, 4, 7, 6, 3, 5, 1
+];
+
 pub enum SKINNY {
-    Skinny64 { r: Option<usize>, lfsrs: Vec<LFSR<4>> },
-    Skinny128 { r: Option<usize>, lfsrs: Vec<LFSR<8>> },
+    Skinny64 { first_round: usize, r: Option<usize>, lfsrs: Vec<LFSR<4>>, inv_lfsrs: Vec<LFSR<4>> },
+    Skinny128 { first_round: usize, r: Option<usize>, lfsrs: Vec<LFSR<8>>, inv_lfsrs: Vec<LFSR<8>> },
 }
 
 impl SKINNY {
-    #[allow(dead_code)]
     pub fn v64() -> SKINNY {
         SKINNY::Skinny64 {
+            first_round: 0,
             r: None,
             lfsrs: vec![
                 LFSR::new([x(2), x(1), x(0), x(3) ^ x(2)]),
                 LFSR::new([x(0) ^ x(3), x(3), x(2), x(1)]),
             ],
+            inv_lfsrs: vec![
+                LFSR::new([x(3) ^ x(0), x(3), x(2), x(1)]),
+                LFSR::new([x(2), x(1), x(0), x(3) ^ x(2)]),
+            ],
         }
     }
 
-    #[allow(dead_code)]
     pub fn v64_with_rounds(rounds: usize) -> SKINNY {
         SKINNY::Skinny64 {
+            first_round: 0,
             r: Some(rounds),
             lfsrs: vec![
                 LFSR::new([x(2), x(1), x(0), x(3) ^ x(2)]),
                 LFSR::new([x(0) ^ x(3), x(3), x(2), x(1)]),
             ],
+            inv_lfsrs: vec![
+                LFSR::new([x(3) ^ x(0), x(3), x(2), x(1)]),
+                LFSR::new([x(2), x(1), x(0), x(3) ^ x(2)]),
+            ],
         }
     }
 
-    #[allow(dead_code)]
     pub fn v128() -> SKINNY {
         SKINNY::Skinny128 {
+            first_round: 0,
             r: None,
             lfsrs: vec![
                 LFSR::new([x(6), x(5), x(4), x(3), x(2), x(1), x(0), x(7) ^ x(5)]),
                 LFSR::new([x(0) ^ x(6), x(7), x(6), x(5), x(4), x(3), x(2), x(1)]),
             ],
+            inv_lfsrs: vec![
+                LFSR::new([x(0) ^ x(6), x(7), x(6), x(5), x(4), x(3), x(2), x(1)]),
+                LFSR::new([x(6), x(5), x(4), x(3), x(2), x(1), x(0), x(7) ^ x(5)]),
+            ],
         }
     }
-    #[allow(dead_code)]
     pub fn v128_with_rounds(rounds: usize) -> SKINNY {
         SKINNY::Skinny128 {
+            first_round: 0,
             r: Some(rounds),
             lfsrs: vec![
                 LFSR::new([x(6), x(5), x(4), x(3), x(2), x(1), x(0), x(7) ^ x(5)]),
                 LFSR::new([x(0) ^ x(6), x(7), x(6), x(5), x(4), x(3), x(2), x(1)]),
             ],
+            inv_lfsrs: vec![
+                LFSR::new([x(0) ^ x(6), x(7), x(6), x(5), x(4), x(3), x(2), x(1)]),
+                LFSR::new([x(6), x(5), x(4), x(3), x(2), x(1), x(0), x(7) ^ x(5)]),
+            ],
+        }
+    }
+    /// Rounds `start..end` of SKINNY-64, keyed with the full cipher tweakey.
+    pub fn v64_rounds(rounds: Range<usize>) -> SKINNY {
+        assert!(rounds.start <= rounds.end);
+        SKINNY::v64_with_rounds(rounds.end - rounds.start).starting_at(rounds.start)
+    }
+
+    /// Rounds `start..end` of SKINNY-128, keyed with the full cipher tweakey.
+    pub fn v128_rounds(rounds: Range<usize>) -> SKINNY {
+        assert!(rounds.start <= rounds.end);
+        SKINNY::v128_with_rounds(rounds.end - rounds.start).starting_at(rounds.start)
+    }
+
+    fn starting_at(mut self, round: usize) -> SKINNY {
+        match &mut self {
+            SKINNY::Skinny64 { first_round, .. } | SKINNY::Skinny128 { first_round, .. } => *first_round = round,
         }
+        self
+    }
+
+    #[inline]
+    fn rounds_range(&self, tk: usize) -> Range<usize> {
+        let first_round = match self {
+            SKINNY::Skinny64 { first_round, .. } | SKINNY::Skinny128 { first_round, .. } => *first_round,
+        };
+        first_round..first_round + self.nr(tk)
     }
+
     #[inline]
     fn nr(&self, tk: usize) -> usize {
         match self {
@@ -103,7 +174,7 @@ impl SKINNY {
     }
 
     #[inline]
-    fn key_schedule(&self, key: &Matrix<u8>, tk: usize) -> Vec<Vec<Matrix<u8>>> {
+    pub(crate) fn key_schedule(&self, key: &Matrix<u8>, tk: usize) -> Vec<Vec<Matrix<u8>>> {
         let flattened_tk = key.values
             .chunks(16)
             .collect::<Vec<_>>();
@@ -112,20 +183,19 @@ impl SKINNY {
                 Matrix::new(4, 4, sub_key.to_vec())
             }).collect::<Vec<_>>();
         round_tweakey.insert(0, Matrix::empty());
-        let mut round_tweakeys = Vec::with_capacity(self.nr(tk));
+        let mut round_tweakeys = Vec::with_capacity(self.rounds_range(tk).end + 1);
         round_tweakeys.push(round_tweakey.clone());
-        for _ in 1..=self.nr(tk) {
-            for z in 1..=tk {
-                let flattened_tk = &round_tweakey[z].values;
+        for _ in 1..=self.rounds_range(tk).end {
+            for lane in &mut round_tweakey[1..=tk] {
                 let permuted = (0..16)
-                    .map(|idx| flattened_tk[PT[idx]])
+                    .map(|idx| lane.values[PT[idx]])
                     .collect::<Vec<_>>();
-                round_tweakey[z] = Matrix::new(4, 4, permuted);
+                *lane = Matrix::new(4, 4, permuted);
             }
-            for z in 2..=tk {
+            for (z, lane) in round_tweakey.iter_mut().enumerate().take(tk + 1).skip(2) {
                 for i in 0..2 {
                     for j in 0..4 {
-                        round_tweakey[z][(i, j)] = self.lfsr(z, round_tweakey[z][(i, j)]);
+                        lane[(i, j)] = self.lfsr(z, lane[(i, j)]);
                     }
                 }
             }
@@ -134,16 +204,54 @@ impl SKINNY {
         round_tweakeys
     }
 
+    #[inline]
+    pub fn inv_key_schedule(&self, key: &Matrix<u8>, tk: usize, nr: usize) -> Vec<Vec<Matrix<u8>>> {
+        let mut round_tweakey = key.values
+            .chunks(16)
+            .map(|sub_key| {
+                Matrix::new(4, 4, sub_key.to_vec())
+            }).collect::<Vec<_>>();
+        round_tweakey.insert(0, Matrix::empty());
+        let mut round_tweakeys = Vec::with_capacity(nr + 1);
+        round_tweakeys.push(round_tweakey.clone());
+        let mut permuted = vec![0; 16];
+        for _ in 1..=nr {
+            for (z, lane) in round_tweakey.iter_mut().enumerate().take(tk + 1).skip(2) {
+                for i in 0..2 {
+                    for j in 0..4 {
+                        lane[(i, j)] = self.inv_lfsr(z, lane[(i, j)]);
+                    }
+                }
+            }
+            for lane in &mut round_tweakey[1..=tk] {
+                for (cell, &idx) in permuted.iter_mut().zip(INV_PT.iter()) {
+                    *cell = lane.values[idx]
+                }
+                swap(&mut lane.values, &mut permuted);
+            }
+            round_tweakeys.push(round_tweakey.clone());
+        }
+        round_tweakeys
+    }
+
     #[inline]
     fn lfsr(&self, i: usize, value: u8) -> u8 {
-        match &self {
-            &SKINNY::Skinny64 { lfsrs, .. } => lfsrs[i - 2].eval(value as usize) as u8,
-            &SKINNY::Skinny128 { lfsrs, .. } => lfsrs[i - 2].eval(value as usize) as u8,
+        match self {
+            SKINNY::Skinny64 { lfsrs, .. } => lfsrs[i - 2].eval(value as usize) as u8,
+            SKINNY::Skinny128 { lfsrs, .. } => lfsrs[i - 2].eval(value as usize) as u8,
         }
     }
 
     #[inline]
-    fn add_round_tweak_key(&self, internal_state: &mut Matrix<u8>, round_tweak_key: &Vec<Matrix<u8>>, tk: usize) {
+    fn inv_lfsr(&self, i: usize, value: u8) -> u8 {
+        match self {
+            SKINNY::Skinny64 { inv_lfsrs, .. } => inv_lfsrs[i - 2].eval(value as usize) as u8,
+            SKINNY::Skinny128 { inv_lfsrs, .. } => inv_lfsrs[i - 2].eval(value as usize) as u8,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn add_round_tweak_key(&self, internal_state: &mut Matrix<u8>, round_tweak_key: &[Matrix<u8>], tk: usize) {
         for i in 0..=1 {
             for j in 0..4 {
                 internal_state[(i, j)] ^= (1..=tk).fold(0, |acc, z| acc ^ round_tweak_key[z][(i, j)]);
@@ -162,7 +270,7 @@ impl SKINNY {
         internal_state[(2, 0)] ^= c2;
     }
     #[inline]
-    fn sub_cells(&self, internal_state: &mut Matrix<u8>) {
+    pub(crate) fn sub_cells(&self, internal_state: &mut Matrix<u8>) {
         match self {
             SKINNY::Skinny64 { .. } => Self::sub_cells_64(internal_state),
             SKINNY::Skinny128 { .. } => Self::sub_cells_128(internal_state),
@@ -179,7 +287,24 @@ impl SKINNY {
             .for_each(|it| *it = SKINNY_128_SBOX[*it as usize])
     }
     #[inline]
-    fn shift_rows(&self, internal_state: &mut Matrix<u8>) {
+    pub(crate) fn inv_sub_cells(&self, internal_state: &mut Matrix<u8>) {
+        match self {
+            SKINNY::Skinny64 { .. } => Self::inv_sub_cells_64(internal_state),
+            SKINNY::Skinny128 { .. } => Self::inv_sub_cells_128(internal_state),
+        }
+    }
+    #[inline]
+    fn inv_sub_cells_64(internal_state: &mut Matrix<u8>) {
+        internal_state.iter_mut()
+            .for_each(|it| *it = INV_SKINNY_64_SBOX[*it as usize])
+    }
+    #[inline]
+    fn inv_sub_cells_128(internal_state: &mut Matrix<u8>) {
+        internal_state.iter_mut()
+            .for_each(|it| *it = INV_SKINNY_128_SBOX[*it as usize])
+    }
+    #[inline]
+    pub(crate) fn shift_rows(&self, internal_state: &mut Matrix<u8>) {
         let mut copy = internal_state.clone();
         for row in 1..4 {
             for col in 0..4 {
@@ -189,7 +314,17 @@ impl SKINNY {
         swap(&mut copy, internal_state);
     }
     #[inline]
-    fn mix_columns(&self, internal_state: &mut Matrix<u8>) {
+    pub(crate) fn inv_shift_rows(&self, internal_state: &mut Matrix<u8>) {
+        let mut copy = internal_state.clone();
+        for row in 1..4 {
+            for col in 0..4 {
+                copy[(row, (col + 4 - row) % 4)] = internal_state[(row, col)];
+            }
+        }
+        swap(&mut copy, internal_state);
+    }
+    #[inline]
+    pub(crate) fn mix_columns(&self, internal_state: &mut Matrix<u8>) {
         let mut tmp: u8;
         for j in 0..4 {
             internal_state[(1, j)] ^= internal_state[(2, j)];
@@ -203,6 +338,88 @@ impl SKINNY {
             internal_state[(0, j)] = tmp;
         }
     }
+    #[inline]
+    pub(crate) fn inv_mix_columns(&self, internal_state: &mut Matrix<u8>) {
+        let mut tmp: u8;
+        for j in 0..4 {
+            internal_state[(0, j)] ^= internal_state[(3, j)];
+            internal_state[(3, j)] ^= internal_state[(1, j)];
+            internal_state[(2, j)] ^= internal_state[(3, j)];
+
+            tmp = internal_state[(0, j)];
+            internal_state[(0, j)] = internal_state[(1, j)];
+            internal_state[(1, j)] = internal_state[(2, j)];
+            internal_state[(2, j)] = internal_state[(3, j)];
+            internal_state[(3, j)] = tmp;
+        }
+    }
+
+    /// Round keys with the round constants folded in.
+    fn round_keys(&self, key: &Matrix<u8>) -> Vec<Matrix<u8>> {
+        let tk = key.values.len() / 16;
+        assert!(tk == 1 || tk == 2 || tk == 3);
+        let round_tweak_keys = self.key_schedule(key, tk);
+        self.rounds_range(tk)
+            .map(|round_num| {
+                let mut round_key = Matrix::new(4, 4, vec![0; 16]);
+                self.add_constants(&mut round_key, round_num);
+                self.add_round_tweak_key(&mut round_key, &round_tweak_keys[round_num], tk);
+                round_key
+            })
+            .collect()
+    }
+
+    pub fn expand(&self, key: &Matrix<u8>) -> ExpandedKey<'_> {
+        ExpandedKey { cipher: self, round_keys: self.round_keys(key) }
+    }
+
+    /// Round keys of SKINNY-64 for the packed `u64` implementation of the state.
+    pub fn expand_packed(&self, key: &Matrix<u8>) -> PackedRoundKeys {
+        assert!(matches!(self, SKINNY::Skinny64 { .. }));
+        PackedRoundKeys::new(&self.round_keys(key))
+    }
+}
+
+/// SKINNY instance with its tweakey schedule already computed.
+pub struct ExpandedKey<'c> {
+    cipher: &'c SKINNY,
+    round_keys: Vec<Matrix<u8>>,
+}
+
+impl ExpandedBlockCipher<Matrix<u8>> for ExpandedKey<'_> {
+    fn encrypt_block(&self, plaintext: &mut Matrix<u8>) {
+        for round_key in &self.round_keys {
+            self.cipher.sub_cells(plaintext);
+            *plaintext ^= round_key;
+            self.cipher.shift_rows(plaintext);
+            self.cipher.mix_columns(plaintext);
+        }
+    }
+
+    fn decrypt_block(&self, ciphertext: &mut Matrix<u8>) {
+        for round_key in self.round_keys.iter().rev() {
+            self.cipher.inv_mix_columns(ciphertext);
+            self.cipher.inv_shift_rows(ciphertext);
+            *ciphertext ^= round_key;
+            self.cipher.inv_sub_cells(ciphertext);
+        }
+    }
+}
+
+impl RoundByRoundBlockCipher<Matrix<u8>> for ExpandedKey<'_> {
+    fn nb_rounds(&self) -> usize {
+        self.round_keys.len()
+    }
+
+    fn sub_cells(&self, _round: usize, state: &mut Matrix<u8>) {
+        self.cipher.sub_cells(state);
+    }
+
+    fn finish_round(&self, round: usize, state: &mut Matrix<u8>) {
+        *state ^= &self.round_keys[round];
+        self.cipher.shift_rows(state);
+        self.cipher.mix_columns(state);
+    }
 }
 
 impl SymmetricCipher<Matrix<u8>, Matrix<u8>> for SKINNY {
@@ -210,7 +427,7 @@ impl SymmetricCipher<Matrix<u8>, Matrix<u8>> for SKINNY {
         let tk = key.values.len() / plaintext.values.len();
         assert!(tk == 1 || tk == 2 || tk == 3);
         let round_tweak_keys = self.key_schedule(key, tk);
-        for round_num in 0..self.nr(tk) {
+        for round_num in self.rounds_range(tk) {
             self.sub_cells(plaintext);
             self.add_constants(plaintext, round_num);
             self.add_round_tweak_key(plaintext, &round_tweak_keys[round_num], tk);
@@ -219,15 +436,49 @@ impl SymmetricCipher<Matrix<u8>, Matrix<u8>> for SKINNY {
         }
     }
 
-    fn decipher(&self, _key: &Matrix<u8>, _plaintext: &mut Matrix<u8>) {
-        todo!()
+    fn decipher(&self, key: &Matrix<u8>, plaintext: &mut Matrix<u8>) {
+        let tk = key.values.len() / plaintext.values.len();
+        assert!(tk == 1 || tk == 2 || tk == 3);
+        let round_tweak_keys = self.key_schedule(key, tk);
+        for round_num in self.rounds_range(tk).rev() {
+            self.inv_mix_columns(plaintext);
+            self.inv_shift_rows(plaintext);
+            self.add_round_tweak_key(plaintext, &round_tweak_keys[round_num], tk);
+            self.add_constants(plaintext, round_num);
+            self.inv_sub_cells(plaintext);
+        }
+    }
+}
+
+impl SymmetricCipher<Matrix<u8>, u64> for SKINNY {
+    fn cipher(&self, key: &Matrix<u8>, plaintext: &mut u64) {
+        self.expand_packed(key).encrypt_block(plaintext);
+    }
+
+    fn decipher(&self, key: &Matrix<u8>, plaintext: &mut u64) {
+        self.expand_packed(key).decrypt_block(plaintext);
+    }
+}
+
+impl TweakableBlockCipher for SKINNY {
+    type Key = Matrix<u8>;
+    type Tweak = Matrix<u8>;
+    type Block = Matrix<u8>;
+
+    fn encrypt(&self, key: &Matrix<u8>, tweak: &Matrix<u8>, block: &mut Matrix<u8>) {
+        self.cipher(&tweakey(key, tweak), block);
+    }
+
+    fn decrypt(&self, key: &Matrix<u8>, tweak: &Matrix<u8>, block: &mut Matrix<u8>) {
+        self.decipher(&tweakey(key, tweak), block);
     }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::ciphers::skinny::SKINNY;
-    use crate::ciphers::SymmetricCipher;
+    use crate::ciphers::{ExpandedBlockCipher, SymmetricCipher, TweakableBlockCipher};
+    use crate::ciphers::packed;
     use crate::matrix::Matrix;
 
     fn parse_nibbles(word: &'static str) -> Vec<u8> {
@@ -309,4 +560,209 @@ mod tests {
         skinny.cipher(&key, &mut plaintext);
         assert_eq!(plaintext, ciphertext);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_decipher_skinny_64_64() {
+        let skinny = SKINNY::v64();
+        let key = Matrix::new(1, 16, parse_nibbles("f5269826fc681238"));
+        let plaintext = Matrix::new(4, 4, parse_nibbles("06034f957724d19d"));
+        let mut ciphertext = Matrix::new(4, 4, parse_nibbles("bb39dfb2429b8ac7"));
+        skinny.decipher(&key, &mut ciphertext);
+        assert_eq!(ciphertext, plaintext);
+    }
+
+    #[test]
+    fn test_decipher_skinny_64_128() {
+        let skinny = SKINNY::v64();
+        let key = Matrix::new(2, 16, parse_nibbles("9eb93640d088da6376a39d1c8bea71e1"));
+        let plaintext = Matrix::new(4, 4, parse_nibbles("cf16cfe8fd0f98aa"));
+        let mut ciphertext = Matrix::new(4, 4, parse_nibbles("6ceda1f43de92b9e"));
+        skinny.decipher(&key, &mut ciphertext);
+        assert_eq!(ciphertext, plaintext);
+    }
+
+    #[test]
+    fn test_decipher_skinny_64_192() {
+        let skinny = SKINNY::v64();
+        let key = Matrix::new(3, 16, parse_nibbles("ed00c85b120d68618753e24bfd908f60b2dbb41b422dfcd0"));
+        let plaintext = Matrix::new(4, 4, parse_nibbles("530c61d35e8663c3"));
+        let mut ciphertext = Matrix::new(4, 4, parse_nibbles("dd2cf1a8f330303c"));
+        skinny.decipher(&key, &mut ciphertext);
+        assert_eq!(ciphertext, plaintext);
+    }
+
+    #[test]
+    fn test_decipher_skinny_128_128() {
+        let skinny = SKINNY::v128();
+        let key = Matrix::new(1, 16, parse_bytes("4f55cfb0520cac52fd92c15f37073e93"));
+        let plaintext = Matrix::new(4, 4, parse_bytes("f20adb0eb08b648a3b2eeed1f0adda14"));
+        let mut ciphertext = Matrix::new(4, 4, parse_bytes("22ff30d498ea62d7e45b476e33675b74"));
+        skinny.decipher(&key, &mut ciphertext);
+        assert_eq!(ciphertext, plaintext);
+    }
+
+    #[test]
+    fn test_decipher_skinny_128_256() {
+        let skinny = SKINNY::v128();
+        let key = Matrix::new(2, 16, parse_bytes("009cec81605d4ac1d2ae9e3085d7a1f31ac123ebfc00fddcf01046ceeddfcab3"));
+        let plaintext = Matrix::new(4, 4, parse_bytes("3a0c47767a26a68dd382a695e7022e25"));
+        let mut ciphertext = Matrix::new(4, 4, parse_bytes("b731d98a4bde147a7ed4a6f16b9b587f"));
+        skinny.decipher(&key, &mut ciphertext);
+        assert_eq!(ciphertext, plaintext);
+    }
+
+    #[test]
+    fn test_decipher_skinny_128_384() {
+        let skinny = SKINNY::v128();
+        let key = Matrix::new(3, 16, parse_bytes("df889548cfc7ea52d296339301797449ab588a34a47f1ab2dfe9c8293fbea9a5ab1afac2611012cd8cef952618c3ebe8"));
+        let plaintext = Matrix::new(4, 4, parse_bytes("a3994b66ad85a3459f44e92b08f550cb"));
+        let mut ciphertext = Matrix::new(4, 4, parse_bytes("94ecf589e2017c601b38c6346a10dcfa"));
+        skinny.decipher(&key, &mut ciphertext);
+        assert_eq!(ciphertext, plaintext);
+    }
+
+    #[test]
+    fn test_round_trip_skinny_with_rounds() {
+        let skinny = SKINNY::v64_with_rounds(7);
+        let key = Matrix::new(3, 16, parse_nibbles("ed00c85b120d68618753e24bfd908f60b2dbb41b422dfcd0"));
+        let mut plaintext = Matrix::new(4, 4, parse_nibbles("530c61d35e8663c3"));
+        let original_plaintext = plaintext.clone();
+        skinny.cipher(&key, &mut plaintext);
+        skinny.decipher(&key, &mut plaintext);
+        assert_eq!(plaintext, original_plaintext);
+
+        let skinny = SKINNY::v128_with_rounds(7);
+        let key = Matrix::new(3, 16, parse_bytes("df889548cfc7ea52d296339301797449ab588a34a47f1ab2dfe9c8293fbea9a5ab1afac2611012cd8cef952618c3ebe8"));
+        let mut plaintext = Matrix::new(4, 4, parse_bytes("a3994b66ad85a3459f44e92b08f550cb"));
+        let original_plaintext = plaintext.clone();
+        skinny.cipher(&key, &mut plaintext);
+        skinny.decipher(&key, &mut plaintext);
+        assert_eq!(plaintext, original_plaintext);
+    }
+
+    #[test]
+    fn test_inv_lfsr() {
+        for skinny in [SKINNY::v64(), SKINNY::v128()] {
+            let size = match &skinny {
+                SKINNY::Skinny64 { .. } => 16,
+                SKINNY::Skinny128 { .. } => 256,
+            };
+            for z in 2..=3 {
+                for value in 0..size {
+                    let value = value as u8;
+                    assert_eq!(value, skinny.inv_lfsr(z, skinny.lfsr(z, value)));
+                    assert_eq!(value, skinny.lfsr(z, skinny.inv_lfsr(z, value)));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_inv_key_schedule_skinny() {
+        let skinny = SKINNY::v128();
+        let key = Matrix::new(3, 16, parse_bytes("df889548cfc7ea52d296339301797449ab588a34a47f1ab2dfe9c8293fbea9a5ab1afac2611012cd8cef952618c3ebe8"));
+
+        fn tki_to_tweakey(tki: &[Matrix<u8>]) -> Matrix<u8> {
+            let values = tki.iter()
+                .flat_map(|tk| tk.values.iter().cloned())
+                .collect::<Vec<_>>();
+            Matrix::new(values.len() / 16, 16, values)
+        }
+
+        let tks = skinny.key_schedule(&key, 3);
+        let last_tweakey = tki_to_tweakey(tks.last().unwrap());
+        let inv_tks = skinny.inv_key_schedule(&last_tweakey, 3, skinny.nr(3));
+        for (round_num, inv_tk) in inv_tks.iter().enumerate() {
+            assert_eq!(tki_to_tweakey(inv_tk), tki_to_tweakey(&tks[skinny.nr(3) - round_num]));
+        }
+    }
+
+    #[test]
+    fn test_inv_mc_and_sr() {
+        let skinny = SKINNY::v64();
+        let mut state = Matrix::new(4, 4, parse_nibbles("06034f957724d19d"));
+        let original_state = state.clone();
+        skinny.shift_rows(&mut state);
+        skinny.mix_columns(&mut state);
+        skinny.inv_mix_columns(&mut state);
+        skinny.inv_shift_rows(&mut state);
+        assert_eq!(state, original_state);
+    }
+
+    #[test]
+    fn test_tweakable_skinny_128_384() {
+        let skinny = SKINNY::v128();
+        let tweak = Matrix::new(1, 16, parse_bytes("df889548cfc7ea52d296339301797449"));
+        let key = Matrix::new(2, 16, parse_bytes("ab588a34a47f1ab2dfe9c8293fbea9a5ab1afac2611012cd8cef952618c3ebe8"));
+        let plaintext = Matrix::new(4, 4, parse_bytes("a3994b66ad85a3459f44e92b08f550cb"));
+        let ciphertext = Matrix::new(4, 4, parse_bytes("94ecf589e2017c601b38c6346a10dcfa"));
+        let mut block = plaintext.clone();
+        skinny.encrypt(&key, &tweak, &mut block);
+        assert_eq!(block, ciphertext);
+        skinny.decrypt(&key, &tweak, &mut block);
+        assert_eq!(block, plaintext);
+
+        let mut block = plaintext.clone();
+        skinny.encrypt(&Matrix::new(3, 16, [tweak.values, key.values].concat()), &Matrix::empty(), &mut block);
+        assert_eq!(block, ciphertext);
+    }
+
+    #[test]
+    fn test_packed_skinny_64() {
+        let skinny = SKINNY::v64();
+        for (key, plaintext, ciphertext) in [
+            ("f5269826fc681238", "06034f957724d19d", "bb39dfb2429b8ac7"),
+            ("9eb93640d088da6376a39d1c8bea71e1", "cf16cfe8fd0f98aa", "6ceda1f43de92b9e"),
+            ("ed00c85b120d68618753e24bfd908f60b2dbb41b422dfcd0", "530c61d35e8663c3", "dd2cf1a8f330303c"),
+        ] {
+            let key = parse_nibbles(key);
+            let key = Matrix::new(key.len() / 16, 16, key);
+            let plaintext = Matrix::new(4, 4, parse_nibbles(plaintext));
+            let ciphertext = Matrix::new(4, 4, parse_nibbles(ciphertext));
+            let mut block = packed::pack(&plaintext);
+            skinny.cipher(&key, &mut block);
+            assert_eq!(packed::unpack(block), ciphertext);
+            skinny.decipher(&key, &mut block);
+            assert_eq!(packed::unpack(block), plaintext);
+        }
+    }
+
+    #[test]
+    fn test_expanded_key_skinny_128_384() {
+        let key = Matrix::new(3, 16, vec![
+            0xdf, 0x88, 0x95, 0x48, 0xcf, 0xc7, 0xea, 0x52, 0xd2, 0x96, 0x33, 0x93, 0x01, 0x79, 0x74, 0x49,
+            0xab, 0x58, 0x8a, 0x34, 0xa4, 0x7f, 0x1a, 0xb2, 0xdf, 0xe9, 0xc8, 0x29, 0x3f, 0xbe, 0xa9, 0xa5,
+            0xab, 0x1a, 0xfa, 0xc2, 0x61, 0x10, 0x12, 0xcd, 0x8c, 0xef, 0x95, 0x26, 0x18, 0xc3, 0xeb, 0xe8,
+        ]);
+        let plaintext = Matrix::new(4, 4, vec![
+            0xa3, 0x99, 0x4b, 0x66, 0xad, 0x85, 0xa3, 0x45, 0x9f, 0x44, 0xe9, 0x2b, 0x08, 0xf5, 0x50, 0xcb,
+        ]);
+        let skinny = SKINNY::v128_with_rounds(17);
+        let mut expected = plaintext.clone();
+        skinny.cipher(&key, &mut expected);
+        let expanded_key = skinny.expand(&key);
+        let mut block = plaintext.clone();
+        expanded_key.encrypt_block(&mut block);
+        assert_eq!(block, expected);
+        expanded_key.decrypt_block(&mut block);
+        assert_eq!(block, plaintext);
+    }
+
+    #[test]
+    fn test_rounds_window_skinny() {
+        let key = Matrix::new(2, 16, parse_nibbles("9eb93640d088da6376a39d1c8bea71e1"));
+        let plaintext = Matrix::new(4, 4, parse_nibbles("cf16cfe8fd0f98aa"));
+        let mut expected = plaintext.clone();
+        SKINNY::v64().cipher(&key, &mut expected);
+
+        let mut block = plaintext.clone();
+        for rounds in [0..10, 10..23, 23..36] {
+            SKINNY::v64_rounds(rounds).cipher(&key, &mut block);
+        }
+        assert_eq!(block, expected);
+        for rounds in [23..36, 10..23, 0..10] {
+            SKINNY::v64_rounds(rounds).decipher(&key, &mut block);
+        }
+        assert_eq!(block, plaintext);
+    }
+}
@@ -1,5 +1,5 @@
 use std::mem::swap;
-use crate::ciphers::SymmetricCipher;
+use crate::ciphers::{SymmetricCipher, TweakableBlockCipher, tweakey};
 use crate::lfsr::{LFSR, x};
 use crate::matrix::Matrix;
 
@@ -7,6 +7,10 @@ const SKINNY_64_SBOX: [u8; 16] = [
     12, 6, 9, 0, 1, 10, 2, 11, 3, 8, 5, 13, 4, 14, 7, 15
 ];
 
+const INV_SKINNY_64_SBOX: [u8; 16] = [
+    3, 4, 6, 8, 12, 10, 1, 14, 9, 2, 5, 7, 0, 11, 13, 15
+];
+
 const RC: [u8; 62] = [
     0x01, 0x03, 0x07, 0x0F, 0x1F, 0x3E, 0x3D, 0x3B, 0x37, 0x2F, 0x1E, 0x3C, 0x39, 0x33,
     0x27, 0x0E, 0x1D, 0x3A, 0x35, 0x2B, 0x16, 0x2C, 0x18, 0x30, 0x21, 0x02, 0x05, 0x0B,
@@ -22,17 +26,20 @@ const PT: [usize; 16] = [
     9, 15, 8, 13, 10, 14, 12, 11, 0, 1, 2, 3, 4, 5, 6, 7
 ];
 
+const INV_PT: [usize; 16] = [
+    8, 9, 10, 11, 12, 13, 14, 15, 2, 0, 4, 7, 6, 3, 5, 1
+];
+
 
 #[allow(non_camel_case_types)]
 pub struct SKINNYe_v2 {
     r: Option<usize>,
     lfsrs: Vec<LFSR<4>>,
+    inv_lfsrs: Vec<LFSR<4>>,
 }
 
-impl SKINNYe_v2 {
-
-    #[allow(dead_code)]
-    pub fn default() -> SKINNYe_v2 {
+impl Default for SKINNYe_v2 {
+    fn default() -> SKINNYe_v2 {
         SKINNYe_v2 {
             r: None,
             lfsrs: vec![
@@ -40,9 +47,16 @@ impl SKINNYe_v2 {
                 LFSR::new([x(0) ^ x(3), x(3), x(2), x(1)]),
                 LFSR::new([x(1), x(0), x(3) ^ x(2), x(2) ^ x(1)]),
             ],
+            inv_lfsrs: vec![
+                LFSR::new([x(3) ^ x(0), x(3), x(2), x(1)]),
+                LFSR::new([x(2), x(1), x(0), x(3) ^ x(2)]),
+                LFSR::new([x(1) ^ x(0) ^ x(3), x(0) ^ x(3), x(3), x(2)]),
+            ],
         }
     }
+}
 
+impl SKINNYe_v2 {
     #[inline]
     fn nr(&self, tk: usize) -> usize {
         self.r.unwrap_or(NR[tk - 1])
@@ -61,17 +75,16 @@ impl SKINNYe_v2 {
         let mut round_tweakeys = Vec::with_capacity(self.nr(tk));
         round_tweakeys.push(round_tweakey.clone());
         for _ in 1..=self.nr(tk) {
-            for z in 1..=tk {
-                let flattened_tk = &round_tweakey[z].values;
+            for lane in &mut round_tweakey[1..=tk] {
                 let permuted = (0..16)
-                    .map(|idx| flattened_tk[PT[idx]])
+                    .map(|idx| lane.values[PT[idx]])
                     .collect::<Vec<_>>();
-                round_tweakey[z] = Matrix::new(4, 4, permuted);
+                *lane = Matrix::new(4, 4, permuted);
             }
-            for z in 2..=tk {
+            for (z, lane) in round_tweakey.iter_mut().enumerate().take(tk + 1).skip(2) {
                 for i in 0..2 {
                     for j in 0..4 {
-                        round_tweakey[z][(i, j)] = self.lfsr(z, round_tweakey[z][(i, j)]);
+                        lane[(i, j)] = self.lfsr(z, lane[(i, j)]);
                     }
                 }
             }
@@ -80,13 +93,48 @@ impl SKINNYe_v2 {
         round_tweakeys
     }
 
+    #[inline]
+    pub fn inv_key_schedule(&self, key: &Matrix<u8>, tk: usize, nr: usize) -> Vec<Vec<Matrix<u8>>> {
+        let mut round_tweakey = key.values
+            .chunks(16)
+            .map(|sub_key| {
+                Matrix::new(4, 4, sub_key.to_vec())
+            }).collect::<Vec<_>>();
+        round_tweakey.insert(0, Matrix::empty());
+        let mut round_tweakeys = Vec::with_capacity(nr + 1);
+        round_tweakeys.push(round_tweakey.clone());
+        let mut permuted = vec![0; 16];
+        for _ in 1..=nr {
+            for (z, lane) in round_tweakey.iter_mut().enumerate().take(tk + 1).skip(2) {
+                for i in 0..2 {
+                    for j in 0..4 {
+                        lane[(i, j)] = self.inv_lfsr(z, lane[(i, j)]);
+                    }
+                }
+            }
+            for lane in &mut round_tweakey[1..=tk] {
+                for (cell, &idx) in permuted.iter_mut().zip(INV_PT.iter()) {
+                    *cell = lane.values[idx]
+                }
+                swap(&mut lane.values, &mut permuted);
+            }
+            round_tweakeys.push(round_tweakey.clone());
+        }
+        round_tweakeys
+    }
+
     #[inline]
     fn lfsr(&self, i: usize, value: u8) -> u8 {
         self.lfsrs[i - 2].eval(value as usize) as u8
     }
 
     #[inline]
-    fn add_round_tweak_key(&self, internal_state: &mut Matrix<u8>, round_tweak_key: &Vec<Matrix<u8>>, tk: usize) {
+    fn inv_lfsr(&self, i: usize, value: u8) -> u8 {
+        self.inv_lfsrs[i - 2].eval(value as usize) as u8
+    }
+
+    #[inline]
+    fn add_round_tweak_key(&self, internal_state: &mut Matrix<u8>, round_tweak_key: &[Matrix<u8>], tk: usize) {
         for i in 0..=1 {
             for j in 0..4 {
                 internal_state[(i, j)] ^= (1..=tk).fold(0, |acc, z| acc ^ round_tweak_key[z][(i, j)]);
@@ -111,6 +159,12 @@ impl SKINNYe_v2 {
             .for_each(|it| *it = SKINNY_64_SBOX[*it as usize])
     }
 
+    #[inline]
+    fn inv_sub_cells(&self, internal_state: &mut Matrix<u8>) {
+        internal_state.iter_mut()
+            .for_each(|it| *it = INV_SKINNY_64_SBOX[*it as usize])
+    }
+
     #[inline]
     fn shift_rows(&self, internal_state: &mut Matrix<u8>) {
         let mut copy = internal_state.clone();
@@ -122,6 +176,17 @@ impl SKINNYe_v2 {
         swap(&mut copy, internal_state);
     }
 
+    #[inline]
+    fn inv_shift_rows(&self, internal_state: &mut Matrix<u8>) {
+        let mut copy = internal_state.clone();
+        for row in 1..4 {
+            for col in 0..4 {
+                copy[(row, (col + 4 - row) % 4)] = internal_state[(row, col)];
+            }
+        }
+        swap(&mut copy, internal_state);
+    }
+
     #[inline]
     fn mix_columns(&self, internal_state: &mut Matrix<u8>) {
         let mut tmp: u8;
@@ -137,6 +202,22 @@ impl SKINNYe_v2 {
             internal_state[(0, j)] = tmp;
         }
     }
+
+    #[inline]
+    fn inv_mix_columns(&self, internal_state: &mut Matrix<u8>) {
+        let mut tmp: u8;
+        for j in 0..4 {
+            internal_state[(0, j)] ^= internal_state[(3, j)];
+            internal_state[(3, j)] ^= internal_state[(1, j)];
+            internal_state[(2, j)] ^= internal_state[(3, j)];
+
+            tmp = internal_state[(0, j)];
+            internal_state[(0, j)] = internal_state[(1, j)];
+            internal_state[(1, j)] = internal_state[(2, j)];
+            internal_state[(2, j)] = internal_state[(3, j)];
+            internal_state[(3, j)] = tmp;
+        }
+    }
 }
 
 impl SymmetricCipher<Matrix<u8>, Matrix<u8>> for SKINNYe_v2 {
@@ -144,24 +225,47 @@ impl SymmetricCipher<Matrix<u8>, Matrix<u8>> for SKINNYe_v2 {
         let tk = key.values.len() / plaintext.values.len();
         assert!(tk == 1 || tk == 2 || tk == 3 || tk == 4);
         let round_tweak_keys = self.key_schedule(key, tk);
-        for round_num in 0..self.nr(tk) {
+        for (round_num, round_tweak_key) in round_tweak_keys.iter().enumerate().take(self.nr(tk)) {
             self.sub_cells(plaintext);
             self.add_constants(plaintext, round_num);
-            self.add_round_tweak_key(plaintext, &round_tweak_keys[round_num], tk);
+            self.add_round_tweak_key(plaintext, round_tweak_key, tk);
             self.shift_rows(plaintext);
             self.mix_columns(plaintext);
         }
     }
 
-    fn decipher(&self, _key: &Matrix<u8>, _plaintext: &mut Matrix<u8>) {
-        todo!()
+    fn decipher(&self, key: &Matrix<u8>, plaintext: &mut Matrix<u8>) {
+        let tk = key.values.len() / plaintext.values.len();
+        assert!(tk == 1 || tk == 2 || tk == 3 || tk == 4);
+        let round_tweak_keys = self.key_schedule(key, tk);
+        for round_num in (0..self.nr(tk)).rev() {
+            self.inv_mix_columns(plaintext);
+            self.inv_shift_rows(plaintext);
+            self.add_round_tweak_key(plaintext, &round_tweak_keys[round_num], tk);
+            self.add_constants(plaintext, round_num);
+            self.inv_sub_cells(plaintext);
+        }
+    }
+}
+
+impl TweakableBlockCipher for SKINNYe_v2 {
+    type Key = Matrix<u8>;
+    type Tweak = Matrix<u8>;
+    type Block = Matrix<u8>;
+
+    fn encrypt(&self, key: &Matrix<u8>, tweak: &Matrix<u8>, block: &mut Matrix<u8>) {
+        self.cipher(&tweakey(key, tweak), block);
+    }
+
+    fn decrypt(&self, key: &Matrix<u8>, tweak: &Matrix<u8>, block: &mut Matrix<u8>) {
+        self.decipher(&tweakey(key, tweak), block);
     }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::ciphers::skinnye_v2::SKINNYe_v2;
-    use crate::ciphers::SymmetricCipher;
+    use crate::ciphers::{SymmetricCipher, TweakableBlockCipher};
     use crate::matrix::Matrix;
 
     fn parse_nibbles(word: &'static str) -> Vec<u8> {
@@ -212,4 +316,89 @@ mod tests {
         skinny.cipher(&key, &mut plaintext);
         assert_eq!(plaintext, ciphertext);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_decipher_skinnye_v2_64() {
+        let skinny = SKINNYe_v2::default();
+        let key = Matrix::new(1, 16, parse_nibbles("f5269826fc681238"));
+        let plaintext = Matrix::new(4, 4, parse_nibbles("06034f957724d19d"));
+        let mut ciphertext = Matrix::new(4, 4, parse_nibbles("bb39dfb2429b8ac7"));
+        skinny.decipher(&key, &mut ciphertext);
+        assert_eq!(ciphertext, plaintext);
+    }
+
+    #[test]
+    fn test_decipher_skinnye_v2_128() {
+        let skinny = SKINNYe_v2::default();
+        let key = Matrix::new(2, 16, parse_nibbles("9eb93640d088da6376a39d1c8bea71e1"));
+        let plaintext = Matrix::new(4, 4, parse_nibbles("cf16cfe8fd0f98aa"));
+        let mut ciphertext = Matrix::new(4, 4, parse_nibbles("6ceda1f43de92b9e"));
+        skinny.decipher(&key, &mut ciphertext);
+        assert_eq!(ciphertext, plaintext);
+    }
+
+    #[test]
+    fn test_decipher_skinnye_v2_192() {
+        let skinny = SKINNYe_v2::default();
+        let key = Matrix::new(3, 16, parse_nibbles("ed00c85b120d68618753e24bfd908f60b2dbb41b422dfcd0"));
+        let plaintext = Matrix::new(4, 4, parse_nibbles("530c61d35e8663c3"));
+        let mut ciphertext = Matrix::new(4, 4, parse_nibbles("dd2cf1a8f330303c"));
+        skinny.decipher(&key, &mut ciphertext);
+        assert_eq!(ciphertext, plaintext);
+    }
+
+    #[test]
+    fn test_decipher_skinnye_v2_256() {
+        let skinny = SKINNYe_v2::default();
+        let key = Matrix::new(4, 16, parse_nibbles("ed00c85b120d68618753e24bfd908f60b2dbb41b422dfcd0ed00c85b120d6861"));
+        let plaintext = Matrix::new(4, 4, parse_nibbles("530c61d35e8663c3"));
+        let mut ciphertext = Matrix::new(4, 4, parse_nibbles("f740f34ebd1430a8"));
+        skinny.decipher(&key, &mut ciphertext);
+        assert_eq!(ciphertext, plaintext);
+    }
+
+    #[test]
+    fn test_inv_lfsr() {
+        let skinny = SKINNYe_v2::default();
+        for z in 2..=4 {
+            for value in 0..16 {
+                assert_eq!(value, skinny.inv_lfsr(z, skinny.lfsr(z, value)));
+                assert_eq!(value, skinny.lfsr(z, skinny.inv_lfsr(z, value)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_inv_key_schedule_skinnye_v2() {
+        let skinny = SKINNYe_v2::default();
+        let key = Matrix::new(4, 16, parse_nibbles("ed00c85b120d68618753e24bfd908f60b2dbb41b422dfcd0ed00c85b120d6861"));
+
+        fn tki_to_tweakey(tki: &[Matrix<u8>]) -> Matrix<u8> {
+            let values = tki.iter()
+                .flat_map(|tk| tk.values.iter().cloned())
+                .collect::<Vec<_>>();
+            Matrix::new(values.len() / 16, 16, values)
+        }
+
+        let tks = skinny.key_schedule(&key, 4);
+        let last_tweakey = tki_to_tweakey(tks.last().unwrap());
+        let inv_tks = skinny.inv_key_schedule(&last_tweakey, 4, skinny.nr(4));
+        for (round_num, inv_tk) in inv_tks.iter().enumerate() {
+            assert_eq!(tki_to_tweakey(inv_tk), tki_to_tweakey(&tks[skinny.nr(4) - round_num]));
+        }
+    }
+
+    #[test]
+    fn test_tweakable_skinnye_v2_256() {
+        let skinny = SKINNYe_v2::default();
+        let tweak = Matrix::new(1, 16, parse_nibbles("ed00c85b120d6861"));
+        let key = Matrix::new(3, 16, parse_nibbles("8753e24bfd908f60b2dbb41b422dfcd0ed00c85b120d6861"));
+        let plaintext = Matrix::new(4, 4, parse_nibbles("530c61d35e8663c3"));
+        let ciphertext = Matrix::new(4, 4, parse_nibbles("f740f34ebd1430a8"));
+        let mut block = plaintext.clone();
+        skinny.encrypt(&key, &tweak, &mut block);
+        assert_eq!(block, ciphertext);
+        skinny.decrypt(&key, &tweak, &mut block);
+        assert_eq!(block, plaintext);
+    }
+}
@@ -1,6 +1,9 @@
 use std::mem::swap;
+use std::ops::Range;
 
-use crate::ciphers::SymmetricCipher;
+use crate::ciphers::{ExpandedBlockCipher, RoundByRoundBlockCipher, SymmetricCipher, TweakableBlockCipher};
+use crate::ciphers::bitsliced::{BitslicedBatch, BitsliceWord};
+use crate::ciphers::packed::PackedRoundKeys;
 use crate::lfsr::{LFSR, x};
 use crate::matrix::Matrix;
 
@@ -24,39 +27,47 @@ const INV_PT: [usize; 16] = [
     8, 9, 10, 11, 12, 13, 14, 15, 2, 0, 4, 7, 6, 3, 5, 1
 ];
 
+/// Secret part of the SKINNYee key: the four 2x4 subkeys K0..K3 and the 3-bit round constant seed.
+pub struct SKINNYeeKey {
+    pub sub_keys: Vec<Matrix<u8>>,
+    pub rc_init: u8,
+}
+
+impl SKINNYeeKey {
+    pub fn new(sub_keys: Vec<Matrix<u8>>, rc_init: u8) -> SKINNYeeKey {
+        assert_eq!(sub_keys.len(), 4);
+        assert!(rc_init <= 0b111);
+        SKINNYeeKey { sub_keys, rc_init }
+    }
+}
+
 #[allow(non_camel_case_types)]
 pub struct SKINNYee {
+    first_round: usize,
     r: Option<usize>,
     lfsrs: Vec<LFSR<4>>,
     inv_lfsrs: Vec<LFSR<4>>,
     rc_lfsr: LFSR<10>,
 }
 
-impl SKINNYee {
-    #[allow(dead_code)]
-    pub fn default() -> SKINNYee {
-        SKINNYee {
-            r: Some(NR),
-            lfsrs: vec![
-                LFSR::new([x(2), x(1), x(0), x(3) ^ x(2)]),
-                LFSR::new([x(0) ^ x(3), x(3), x(2), x(1)]),
-                LFSR::new([x(1), x(0), x(3) ^ x(2), x(2) ^ x(1)]),
-            ],
-            inv_lfsrs: vec![
-                LFSR::new([x(3) ^ x(0), x(3), x(2), x(1)]),
-                LFSR::new([x(2), x(1), x(0), x(3) ^ x(2)]),
-                LFSR::new([x(1) ^ x(0) ^ x(3), x(0) ^ x(3), x(3), x(2)]),
-            ],
-            rc_lfsr: LFSR::new([
-                x(8), x(7), x(6), x(5), x(4), x(3), x(2), x(1), x(0), x(9) ^ x(3) ^ x(2) ^ x(0)
-            ]),
-        }
+impl Default for SKINNYee {
+    fn default() -> SKINNYee {
+        SKINNYee::rounds(0..NR)
     }
+}
 
-    #[allow(dead_code)]
+impl SKINNYee {
     pub fn with_rounds(r: usize) -> SKINNYee {
+        SKINNYee::rounds(0..r)
+    }
+
+    /// Rounds `start..end` of the full cipher, keyed with the full cipher key and tweakey:
+    /// round constants, subkeys and round tweakeys are those of the full cipher at these rounds.
+    pub fn rounds(rounds: Range<usize>) -> SKINNYee {
+        assert!(rounds.start <= rounds.end);
         SKINNYee {
-            r: Some(r),
+            first_round: rounds.start,
+            r: Some(rounds.end - rounds.start),
             lfsrs: vec![
                 LFSR::new([x(2), x(1), x(0), x(3) ^ x(2)]),
                 LFSR::new([x(0) ^ x(3), x(3), x(2), x(1)]),
@@ -79,7 +90,12 @@ impl SKINNYee {
     }
 
     #[inline]
-    fn nr_tweak_key_schedule(&self, key: &Matrix<u8>, nr: usize) -> Vec<Vec<Matrix<u8>>> {
+    fn rounds_range(&self) -> Range<usize> {
+        self.first_round..self.first_round + self.nr()
+    }
+
+    #[inline]
+    pub(crate) fn nr_tweak_key_schedule(&self, key: &Matrix<u8>, nr: usize) -> Vec<Vec<Matrix<u8>>> {
         let mut round_tweakey = key.values
             .chunks(16)
             .map(|sub_key| {
@@ -90,17 +106,16 @@ impl SKINNYee {
         round_tweakeys.push(round_tweakey.clone());
         let mut permuted = vec![0; 16];
         for _ in 1..=nr {
-            for z in 1..=TK {
-                let flattened_tk = &round_tweakey[z].values;
-                for idx in 0..16 {
-                    permuted[idx] = flattened_tk[PT[idx]]
+            for lane in &mut round_tweakey[1..=TK] {
+                for (cell, &idx) in permuted.iter_mut().zip(PT.iter()) {
+                    *cell = lane.values[idx]
                 }
-                swap(&mut round_tweakey[z].values, &mut permuted);
+                swap(&mut lane.values, &mut permuted);
             }
-            for z in 2..=TK {
+            for (z, lane) in round_tweakey.iter_mut().enumerate().take(TK + 1).skip(2) {
                 for i in 0..2 {
                     for j in 0..4 {
-                        round_tweakey[z][(i, j)] = self.lfsr(z, round_tweakey[z][(i, j)]);
+                        lane[(i, j)] = self.lfsr(z, lane[(i, j)]);
                     }
                 }
             }
@@ -110,7 +125,6 @@ impl SKINNYee {
     }
 
     #[inline]
-    #[allow(dead_code)]
     pub fn inv_nr_tweak_key_schedule(&self, key: &Matrix<u8>, nr: usize) -> Vec<Vec<Matrix<u8>>> {
         let mut round_tweakey = key.values
             .chunks(16)
@@ -122,19 +136,18 @@ impl SKINNYee {
         round_tweakeys.push(round_tweakey.clone());
         let mut permuted = vec![0; 16];
         for _ in 1..=nr {
-            for z in 2..=TK {
+            for (z, lane) in round_tweakey.iter_mut().enumerate().take(TK + 1).skip(2) {
                 for i in 0..2 {
                     for j in 0..4 {
-                        round_tweakey[z][(i, j)] = self.inv_lfsr(z, round_tweakey[z][(i, j)]);
+                        lane[(i, j)] = self.inv_lfsr(z, lane[(i, j)]);
                     }
                 }
             }
-            for z in 1..=TK {
-                let flattened_tk = &round_tweakey[z].values;
-                for idx in 0..16 {
-                    permuted[idx] = flattened_tk[INV_PT[idx]]
+            for lane in &mut round_tweakey[1..=TK] {
+                for (cell, &idx) in permuted.iter_mut().zip(INV_PT.iter()) {
+                    *cell = lane.values[idx]
                 }
-                swap(&mut round_tweakey[z].values, &mut permuted);
+                swap(&mut lane.values, &mut permuted);
             }
             round_tweakeys.push(round_tweakey.clone());
         }
@@ -143,7 +156,7 @@ impl SKINNYee {
 
     #[inline]
     fn tweak_key_schedule(&self, key: &Matrix<u8>) -> Vec<Vec<Matrix<u8>>> {
-        self.nr_tweak_key_schedule(key, self.nr())
+        self.nr_tweak_key_schedule(key, self.rounds_range().end)
     }
 
     #[inline]
@@ -157,7 +170,7 @@ impl SKINNYee {
     }
 
     #[inline]
-    fn add_round_key(&self, internal_state: &mut Matrix<u8>, round_tweak_key: &Vec<Matrix<u8>>, key: &Matrix<u8>) {
+    fn add_round_key(&self, internal_state: &mut Matrix<u8>, round_tweak_key: &[Matrix<u8>], key: &Matrix<u8>) {
         for i in 0..=1 {
             for j in 0..4 {
                 internal_state[(i, j)] ^= (1..=TK).fold(0, |acc, z| acc ^ round_tweak_key[z][(i, j)]);
@@ -204,7 +217,7 @@ impl SKINNYee {
     }
 
     #[inline]
-    fn inv_shift_rows(&self, internal_state: &mut Matrix<u8>) {
+    pub(crate) fn inv_shift_rows(&self, internal_state: &mut Matrix<u8>) {
         let mut copy = internal_state.clone();
         for row in 1..4 {
             for col in 0..4 {
@@ -305,16 +318,20 @@ impl SKINNYee {
             }
         }
         let tweak_key = Matrix::new(16, 4, tweak_key_values);
-        let mut rc_init = full_key[(24, 0)] as u16;
-        assert!(rc_init <= 0b111);
-        rc_init = (rc_init << 7) | 1;
+        let rc_init = Self::rc_lfsr_init(full_key[(24, 0)]);
         (sub_keys, tweak_key, rc_init)
     }
 
+    #[inline]
+    fn rc_lfsr_init(rc_init: u8) -> u16 {
+        assert!(rc_init <= 0b111);
+        ((rc_init as u16) << 7) | 1
+    }
+
     fn generate_constants(&self, rc_init: u16) -> Vec<Matrix<u8>> {
-        let mut round_constants = Vec::with_capacity(self.nr());
+        let mut round_constants = Vec::with_capacity(self.rounds_range().end);
         let mut rci = rc_init;
-        for _ in 0..self.nr() {
+        for _ in 0..self.rounds_range().end {
             let mut current_round_constants = Vec::with_capacity(16);
             for _ in 0..4 {
                 for _ in 0..4 {
@@ -326,40 +343,137 @@ impl SKINNYee {
         }
         round_constants
     }
+
+    /// Expands a packed key (see `split_key`) once, so that many blocks can be processed under it.
+    pub fn expand(&self, full_key: &Matrix<u8>) -> ExpandedKey<'_> {
+        let (key, tweak, rc_init) = self.split_key(full_key);
+        self.expand_parts(&key, &tweak, rc_init)
+    }
+
+    pub fn expand_tweakable(&self, key: &SKINNYeeKey, tweak: &Matrix<u8>) -> ExpandedKey<'_> {
+        self.expand_parts(&key.sub_keys, tweak, Self::rc_lfsr_init(key.rc_init))
+    }
+
+    fn expand_parts(&self, key: &[Matrix<u8>], tweak: &Matrix<u8>, rc_init: u16) -> ExpandedKey<'_> {
+        let round_tweak_keys = self.tweak_key_schedule(tweak);
+        let rc = self.generate_constants(rc_init);
+        let round_keys = self.rounds_range()
+            .map(|round_num| {
+                let mut round_key = Matrix::new(4, 4, vec![0; 16]);
+                self.add_constants(&mut round_key, &rc[round_num]);
+                self.add_round_key(&mut round_key, &round_tweak_keys[round_num], &key[round_num % 4]);
+                round_key
+            })
+            .collect::<Vec<_>>();
+        let packed_round_keys = PackedRoundKeys::new(&round_keys);
+        ExpandedKey { cipher: self, round_keys, packed_round_keys }
+    }
+}
+
+/// Round keys of a SKINNYee instance, with the round constants already folded in.
+/// Blocks are either `Matrix<u8>` states (reference implementation), packed `u64` states or bitsliced batches.
+pub struct ExpandedKey<'c> {
+    cipher: &'c SKINNYee,
+    round_keys: Vec<Matrix<u8>>,
+    packed_round_keys: PackedRoundKeys,
+}
+
+impl ExpandedBlockCipher<Matrix<u8>> for ExpandedKey<'_> {
+    fn encrypt_block(&self, plaintext: &mut Matrix<u8>) {
+        for round_key in &self.round_keys {
+            self.cipher.sub_cells(plaintext);
+            *plaintext ^= round_key;
+            self.cipher.shift_rows(plaintext);
+            self.cipher.mix_columns(plaintext);
+        }
+    }
+
+    fn decrypt_block(&self, ciphertext: &mut Matrix<u8>) {
+        for round_key in self.round_keys.iter().rev() {
+            self.cipher.inv_mix_columns(ciphertext);
+            self.cipher.inv_shift_rows(ciphertext);
+            *ciphertext ^= round_key;
+            self.cipher.inv_sub_cells(ciphertext);
+        }
+    }
+}
+
+impl RoundByRoundBlockCipher<Matrix<u8>> for ExpandedKey<'_> {
+    fn nb_rounds(&self) -> usize {
+        self.round_keys.len()
+    }
+
+    fn sub_cells(&self, _round: usize, state: &mut Matrix<u8>) {
+        self.cipher.sub_cells(state);
+    }
+
+    fn finish_round(&self, round: usize, state: &mut Matrix<u8>) {
+        *state ^= &self.round_keys[round];
+        self.cipher.shift_rows(state);
+        self.cipher.mix_columns(state);
+    }
+}
+
+impl ExpandedBlockCipher<u64> for ExpandedKey<'_> {
+    fn encrypt_block(&self, plaintext: &mut u64) {
+        self.packed_round_keys.encrypt_block(plaintext);
+    }
+
+    fn decrypt_block(&self, ciphertext: &mut u64) {
+        self.packed_round_keys.decrypt_block(ciphertext);
+    }
+}
+
+impl<W: BitsliceWord> ExpandedBlockCipher<BitslicedBatch<W>> for ExpandedKey<'_> {
+    fn encrypt_block(&self, plaintext: &mut BitslicedBatch<W>) {
+        self.packed_round_keys.encrypt_block(plaintext);
+    }
+
+    fn decrypt_block(&self, ciphertext: &mut BitslicedBatch<W>) {
+        self.packed_round_keys.decrypt_block(ciphertext);
+    }
 }
 
 impl SymmetricCipher<Matrix<u8>, Matrix<u8>> for SKINNYee {
     fn cipher(&self, key: &Matrix<u8>, plaintext: &mut Matrix<u8>) {
-        let (key, tweak, rc_init) = self.split_key(key);
-        let round_tweak_keys = self.tweak_key_schedule(&tweak);
-        let rc = self.generate_constants(rc_init);
-        for round_num in 0..self.nr() {
-            self.sub_cells(plaintext);
-            self.add_constants(plaintext, &rc[round_num]);
-            self.add_round_key(plaintext, &round_tweak_keys[round_num], &key[round_num % 4]);
-            self.shift_rows(plaintext);
-            self.mix_columns(plaintext);
-        }
+        self.expand(key).encrypt_block(plaintext);
     }
 
     fn decipher(&self, key: &Matrix<u8>, plaintext: &mut Matrix<u8>) {
-        let (key, tweak, rc_init) = self.split_key(key);
-        let round_tweak_keys = self.tweak_key_schedule(&tweak);
-        let rc = self.generate_constants(rc_init);
-        for round_num in (0..self.nr()).rev() {
-            self.inv_mix_columns(plaintext);
-            self.inv_shift_rows(plaintext);
-            self.add_round_key(plaintext, &round_tweak_keys[round_num], &key[round_num % 4]);
-            self.add_constants(plaintext, &rc[round_num]);
-            self.inv_sub_cells(plaintext);
-        }
+        self.expand(key).decrypt_block(plaintext);
+    }
+}
+
+impl SymmetricCipher<Matrix<u8>, u64> for SKINNYee {
+    fn cipher(&self, key: &Matrix<u8>, plaintext: &mut u64) {
+        self.expand(key).encrypt_block(plaintext);
+    }
+
+    fn decipher(&self, key: &Matrix<u8>, plaintext: &mut u64) {
+        self.expand(key).decrypt_block(plaintext);
+    }
+}
+
+impl TweakableBlockCipher for SKINNYee {
+    type Key = SKINNYeeKey;
+    type Tweak = Matrix<u8>;
+    type Block = Matrix<u8>;
+
+    fn encrypt(&self, key: &SKINNYeeKey, tweak: &Matrix<u8>, block: &mut Matrix<u8>) {
+        self.expand_tweakable(key, tweak).encrypt_block(block);
+    }
+
+    fn decrypt(&self, key: &SKINNYeeKey, tweak: &Matrix<u8>, block: &mut Matrix<u8>) {
+        self.expand_tweakable(key, tweak).decrypt_block(block);
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::ciphers::skinnyee::SKINNYee;
-    use crate::ciphers::SymmetricCipher;
+    use crate::ciphers::skinnyee::{SKINNYee, SKINNYeeKey};
+    use crate::ciphers::{ExpandedBlockCipher, SymmetricCipher, TweakableBlockCipher};
+    use crate::ciphers::bitsliced::BitslicedBatch;
+    use crate::ciphers::packed;
     use crate::matrix::Matrix;
 
     fn parse_nibbles(word: &'static str) -> Vec<u8> {
@@ -479,4 +593,174 @@ mod tests {
         let first_inv_tweak = tki_to_tweakey(inv_tks.last().unwrap());
         assert_eq!(first_inv_tweak, tweakey);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_rounds_window_skinnyee() {
+        let key = Matrix::new(25, 4, parse_nibbles("\
+        aaaaaaaa\
+        bbbbbbbb\
+        cccccccc\
+        dddddddd\
+        0000000000000000\
+        1111111111111111\
+        2222222222222222\
+        3333333333333333\
+        1000
+        "));
+        let mut plaintext = Matrix::new(4, 4, parse_nibbles("06034f957724d19d"));
+        let ciphertext = Matrix::new(4, 4, parse_nibbles("4510c411d8877458"));
+        SKINNYee::rounds(0..13).cipher(&key, &mut plaintext);
+        SKINNYee::rounds(13..30).cipher(&key, &mut plaintext);
+        SKINNYee::rounds(30..56).cipher(&key, &mut plaintext);
+        assert_eq!(plaintext, ciphertext);
+
+        let window = SKINNYee::rounds(13..30);
+        let mut state = Matrix::new(4, 4, parse_nibbles("06034f957724d19d"));
+        let original_state = state.clone();
+        window.cipher(&key, &mut state);
+        assert_ne!(state, original_state);
+        window.decipher(&key, &mut state);
+        assert_eq!(state, original_state);
+    }
+
+    #[test]
+    fn test_tweakable_skinnyee() {
+        let skinny = SKINNYee::default();
+        let key = SKINNYeeKey::new(vec![
+            Matrix::new(2, 4, parse_nibbles("aaaaaaaa")),
+            Matrix::new(2, 4, parse_nibbles("bbbbbbbb")),
+            Matrix::new(2, 4, parse_nibbles("cccccccc")),
+            Matrix::new(2, 4, parse_nibbles("dddddddd")),
+        ], 1);
+        let tweak = Matrix::new(16, 4, parse_nibbles("\
+        0000000000000000\
+        1111111111111111\
+        2222222222222222\
+        3333333333333333"));
+        let plaintext = Matrix::new(4, 4, parse_nibbles("06034f957724d19d"));
+        let ciphertext = Matrix::new(4, 4, parse_nibbles("4510c411d8877458"));
+        let mut block = plaintext.clone();
+        skinny.encrypt(&key, &tweak, &mut block);
+        assert_eq!(block, ciphertext);
+        skinny.decrypt(&key, &tweak, &mut block);
+        assert_eq!(block, plaintext);
+    }
+
+    #[test]
+    fn test_expanded_key_skinnyee() {
+        let key = Matrix::new(25, 4, parse_nibbles("\
+        aaaaaaaa\
+        bbbbbbbb\
+        cccccccc\
+        dddddddd\
+        0000000000000000\
+        1111111111111111\
+        2222222222222222\
+        3333333333333333\
+        1000
+        "));
+        let skinny = SKINNYee::default();
+        let expanded_key = skinny.expand(&key);
+        let plaintext = Matrix::new(4, 4, parse_nibbles("06034f957724d19d"));
+        let ciphertext = Matrix::new(4, 4, parse_nibbles("4510c411d8877458"));
+        for _ in 0..2 {
+            let mut block = plaintext.clone();
+            expanded_key.encrypt_block(&mut block);
+            assert_eq!(block, ciphertext);
+            expanded_key.decrypt_block(&mut block);
+            assert_eq!(block, plaintext);
+        }
+
+        let window = SKINNYee::rounds(13..30);
+        let mut expected = plaintext.clone();
+        window.cipher(&key, &mut expected);
+        let mut block = plaintext.clone();
+        window.expand(&key).encrypt_block(&mut block);
+        assert_eq!(block, expected);
+    }
+
+    #[test]
+    fn test_packed_operations_match_reference() {
+        let skinny = SKINNYee::default();
+        let mut packed_state = 0x06034f957724d19du64;
+        for _ in 0..64 {
+            let mut state = packed::unpack(packed_state);
+            skinny.sub_cells(&mut state);
+            assert_eq!(packed::pack(&state), packed::sub_cells(packed_state));
+            skinny.shift_rows(&mut state);
+            assert_eq!(packed::pack(&state), packed::shift_rows(packed::sub_cells(packed_state)));
+            let mut state = packed::unpack(packed_state);
+            skinny.mix_columns(&mut state);
+            assert_eq!(packed::pack(&state), packed::mix_columns(packed_state));
+            packed_state = packed_state.wrapping_mul(0x9e3779b97f4a7c15).rotate_left(17);
+        }
+    }
+
+    #[test]
+    fn test_packed_skinnyee() {
+        let key = Matrix::new(25, 4, parse_nibbles("\
+        aaaaaaaa\
+        bbbbbbbb\
+        cccccccc\
+        dddddddd\
+        0000000000000000\
+        1111111111111111\
+        2222222222222222\
+        3333333333333333\
+        1000
+        "));
+        let plaintext = Matrix::new(4, 4, parse_nibbles("06034f957724d19d"));
+        let ciphertext = Matrix::new(4, 4, parse_nibbles("4510c411d8877458"));
+        let skinny = SKINNYee::default();
+        let mut block = packed::pack(&plaintext);
+        skinny.cipher(&key, &mut block);
+        assert_eq!(packed::unpack(block), ciphertext);
+        skinny.decipher(&key, &mut block);
+        assert_eq!(packed::unpack(block), plaintext);
+
+        let window = SKINNYee::rounds(13..30);
+        let expanded_key = window.expand(&key);
+        let mut packed_state = 0x06034f957724d19du64;
+        for _ in 0..64 {
+            let mut state = packed::unpack(packed_state);
+            expanded_key.encrypt_block(&mut state);
+            let mut block = packed_state;
+            expanded_key.encrypt_block(&mut block);
+            assert_eq!(packed::pack(&state), block);
+            packed_state = packed_state.wrapping_mul(0x9e3779b97f4a7c15).rotate_left(17);
+        }
+    }
+
+    #[test]
+    fn test_bitsliced_skinnyee() {
+        let key = Matrix::new(25, 4, parse_nibbles("\
+        aaaaaaaa\
+        bbbbbbbb\
+        cccccccc\
+        dddddddd\
+        0000000000000000\
+        1111111111111111\
+        2222222222222222\
+        3333333333333333\
+        1000
+        "));
+        let skinny = SKINNYee::default();
+        let expanded_key = skinny.expand(&key);
+        let mut packed_state = 0x06034f957724d19du64;
+        let plaintexts = (0..128).map(|_| {
+            packed_state = packed_state.wrapping_mul(0x9e3779b97f4a7c15).rotate_left(17);
+            packed_state
+        }).collect::<Vec<_>>();
+        let ciphertexts = plaintexts.iter().map(|it| {
+            let mut block = *it;
+            expanded_key.encrypt_block(&mut block);
+            block
+        }).collect::<Vec<_>>();
+
+        let mut batch = BitslicedBatch::<u128>::new(&plaintexts);
+        expanded_key.encrypt_block(&mut batch);
+        assert_eq!(batch.blocks(), ciphertexts);
+        expanded_key.decrypt_block(&mut batch);
+        assert_eq!(batch.blocks(), plaintexts);
+    }
+}
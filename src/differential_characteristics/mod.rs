@@ -1,3 +1,10 @@
 pub mod sk_skinny;
+pub mod rtk_skinny;
+pub mod rtk_boom_skinny;
 pub mod sk_rtk_skinnyee;
-pub mod sk_boom_rtk_skinnyee;
\ No newline at end of file
+pub mod sk_boom_rtk_skinnyee;
+pub mod sk_gift;
+pub mod rk_gift;
+pub mod rt_craft;
+pub mod probability;
+pub mod validation;
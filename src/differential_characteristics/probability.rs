@@ -0,0 +1,153 @@
+use std::fmt;
+
+use crate::sbox::Sbox;
+
+/// Active S-box whose transition has a null DDT entry.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ImpossibleTransition {
+    pub round: usize,
+    pub row: usize,
+    pub col: usize,
+    pub input: u8,
+    pub output: u8,
+}
+
+/// Probability of a characteristic computed from the DDT, assuming independent rounds.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TheoreticalProbability {
+    /// `-log2` of the probability of each round, infinite if the round has an impossible transition.
+    pub round_weights: Vec<f64>,
+    pub impossible_transitions: Vec<ImpossibleTransition>,
+}
+
+impl TheoreticalProbability {
+    pub fn weight(&self) -> f64 {
+        self.round_weights.iter().sum()
+    }
+
+    /// `log2` of the probability, `-inf` for an impossible characteristic.
+    pub fn log2(&self) -> f64 {
+        -self.weight()
+    }
+
+    pub fn is_possible(&self) -> bool {
+        self.impossible_transitions.is_empty()
+    }
+
+    /// Whether the weight is the `objective` stored with the characteristic by the search tool.
+    /// Objectives are integers while 8-bit DDT entries such as 6 give fractional weights, so any
+    /// difference below 1 is accepted.
+    pub fn matches_objective(&self, objective: usize) -> bool {
+        (self.weight() - objective as f64).abs() < 1.0
+    }
+}
+
+/// `2^{-w} (w_0 + ... + w_r-1)`, followed by the impossible transitions if any.
+impl fmt::Display for TheoreticalProbability {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let round_weights = self.round_weights.iter().map(|w| w.to_string()).collect::<Vec<_>>();
+        write!(f, "2^{{{}}} ({})", self.log2(), round_weights.join(" + "))?;
+        for t in &self.impossible_transitions {
+            write!(f, "\n  impossible transition in round {} at ({}, {}) : {:#x} -> {:#x}", t.round, t.row, t.col, t.input, t.output)?;
+        }
+        Ok(())
+    }
+}
+
+/// Walks the `X -> SC` transitions of the first `x.len() - 1` rounds of a characteristic and
+/// weights every active S-box by `log2(2^n / DDT[X][SC])`.
+pub fn theoretical_probability(x: &[Vec<Vec<u8>>], sc: &[Vec<Vec<u8>>], sbox: &Sbox) -> TheoreticalProbability {
+    let ddt = sbox.ddt();
+    let rounds = x.len().saturating_sub(1);
+    assert!(sc.len() >= rounds, "SC has {} rounds, {} expected", sc.len(), rounds);
+
+    let mut impossible_transitions = vec![];
+    let round_weights = x.iter().zip(sc).take(rounds).enumerate()
+        .map(|(round, (x, sc))| {
+            let mut weight = 0.0;
+            for (row, (x, sc)) in x.iter().zip(sc).enumerate() {
+                for (col, (&input, &output)) in x.iter().zip(sc).enumerate() {
+                    if input == 0 && output == 0 {
+                        continue;
+                    }
+                    match ddt[input as usize][output as usize] {
+                        0 => {
+                            impossible_transitions.push(ImpossibleTransition { round, row, col, input, output });
+                            weight = f64::INFINITY;
+                        }
+                        count => weight += sbox.bits() as f64 - (count as f64).log2(),
+                    }
+                }
+            }
+            weight
+        })
+        .collect();
+
+    TheoreticalProbability { round_weights, impossible_transitions }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::differential_characteristics::probability::{ImpossibleTransition, theoretical_probability};
+    use crate::sbox::Sbox;
+
+    fn state(cells: &[(usize, usize, u8)]) -> Vec<Vec<u8>> {
+        let mut state = vec![vec![0; 4]; 4];
+        cells.iter().for_each(|&(row, col, value)| state[row][col] = value);
+        state
+    }
+
+    #[test]
+    fn test_theoretical_probability() {
+        let sbox = Sbox::skinny_64();
+        let ddt = sbox.ddt();
+        // One active S-box per round with DDT entries 4 and 2: weights 2 and 3
+        let (a, b) = (1..16).flat_map(|a| (1..16).map(move |b| (a, b)))
+            .find(|&(a, b)| ddt[a][b] == 4)
+            .unwrap();
+        let (c, d) = (1..16).flat_map(|c| (1..16).map(move |d| (c, d)))
+            .find(|&(c, d)| ddt[c][d] == 2)
+            .unwrap();
+        let x = vec![state(&[(0, 0, a as u8)]), state(&[(1, 2, c as u8)]), state(&[])];
+        let sc = vec![state(&[(0, 0, b as u8)]), state(&[(1, 2, d as u8)])];
+        let probability = theoretical_probability(&x, &sc, &sbox);
+        assert_eq!(probability.round_weights, vec![2.0, 3.0]);
+        assert_eq!(probability.log2(), -5.0);
+        assert!(probability.is_possible());
+        assert_eq!(probability.to_string(), "2^{-5} (2 + 3)");
+        assert!(probability.matches_objective(5));
+        assert!(!probability.matches_objective(4));
+        assert!(!probability.matches_objective(6));
+
+        // A DDT entry of 6 gives a fractional weight, the objective is rounded by the search tool
+        let sbox = Sbox::skinny_128();
+        let ddt = sbox.ddt();
+        let (a, b) = (1..256).flat_map(|a| (1..256).map(move |b| (a, b)))
+            .find(|&(a, b)| ddt[a][b] == 6)
+            .unwrap();
+        let x = vec![state(&[(2, 1, a as u8)]), state(&[])];
+        let sc = vec![state(&[(2, 1, b as u8)])];
+        let probability = theoretical_probability(&x, &sc, &sbox);
+        assert_eq!(probability.weight(), 8.0 - 6f64.log2());
+        assert!(probability.matches_objective(5) && probability.matches_objective(6));
+        assert!(!probability.matches_objective(7));
+
+        let zero = vec![state(&[]); 3];
+        assert_eq!(theoretical_probability(&zero, &zero, &sbox).weight(), 0.0);
+    }
+
+    #[test]
+    fn test_impossible_transition() {
+        let sbox = Sbox::skinny_128();
+        let ddt = sbox.ddt();
+        let b = (1..256).find(|&b| ddt[1][b] == 0).unwrap();
+        let x = vec![state(&[]), state(&[(3, 1, 1)]), state(&[])];
+        let sc = vec![state(&[]), state(&[(3, 1, b as u8)])];
+        let probability = theoretical_probability(&x, &sc, &sbox);
+        assert!(!probability.is_possible());
+        assert_eq!(probability.round_weights[0], 0.0);
+        assert_eq!(probability.round_weights[1], f64::INFINITY);
+        assert!(!probability.matches_objective(0));
+        assert_eq!(probability.impossible_transitions, vec![ImpossibleTransition { round: 1, row: 3, col: 1, input: 1, output: b as u8 }]);
+    }
+}
@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+
+use crate::differential_characteristics::validation::{ValidationError, check_cell, check_len, check_states, into_result};
+
+/// `T` is the 16-nibble tweak difference, the same at every round up to the permutation Q.
+#[derive(Serialize, Deserialize)]
+pub struct RelatedTweakCraftDifferentialCharacteristic {
+    #[serde(rename="X")]
+    pub x: Vec<Vec<Vec<u8>>>,
+    #[serde(rename="SC")]
+    pub sc: Vec<Vec<Vec<u8>>>,
+    #[serde(rename="T")]
+    pub t: Vec<u8>,
+    pub objective: usize
+}
+
+impl RelatedTweakCraftDifferentialCharacteristic {
+    /// Checks the dimensions of the characteristic.
+    pub fn validate(&self) -> Result<(), Vec<ValidationError>> {
+        let mut errors = vec![];
+        let rounds = self.x.len().saturating_sub(1);
+        check_states(&mut errors, "X", &self.x, 1, 4);
+        check_states(&mut errors, "SC", &self.sc, rounds, 4);
+        check_len(&mut errors, || "T".to_string(), 16, self.t.len());
+        for (i, &value) in self.t.iter().enumerate() {
+            check_cell(&mut errors, || format!("T[{}]", i), value, 4);
+        }
+        into_result(errors)
+    }
+}
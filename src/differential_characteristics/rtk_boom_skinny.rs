@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+use crate::differential_characteristics::rtk_skinny::RelatedTweakeySkinnyDifferentialCharacteristic;
+use crate::differential_characteristics::validation::{ValidationError, check_boomerang_rounds, check_rounds, into_result};
+
+#[derive(Serialize, Deserialize)]
+pub struct RelatedTweakeySkinnyBoomerangCharacteristic {
+    #[serde(rename="E0EM")]
+    pub e0_em: RelatedTweakeySkinnyDifferentialCharacteristic,
+    #[serde(rename="EME1")]
+    pub em_e1: RelatedTweakeySkinnyDifferentialCharacteristic,
+    pub r0: usize,
+    pub rm: usize,
+    pub r1: usize,
+}
+
+impl RelatedTweakeySkinnyBoomerangCharacteristic {
+    /// Checks both characteristics and that `r0`, `rm` and `r1` fit them, `bits` being 4 for
+    /// SKINNY-64 and 8 for SKINNY-128.
+    pub fn validate(&self, bits: usize) -> Result<(), Vec<ValidationError>> {
+        let mut errors = self.e0_em.check("E0EM.", bits);
+        errors.extend(self.em_e1.check("EME1.", bits));
+        if self.e0_em.x.is_empty() || self.em_e1.x.is_empty() {
+            return into_result(errors);
+        }
+        let valid = check_boomerang_rounds(&mut errors, self.r0, self.rm, self.r1, self.e0_em.x.len() - 1, self.em_e1.x.len() - 1);
+        if valid {
+            // The middle rounds start at round r0 - rm of the first characteristic
+            for (z, lane) in self.e0_em.tk.iter().enumerate() {
+                check_rounds(&mut errors, || format!("E0EM.TK[{}]", z), self.r0 - self.rm + 1, lane.len());
+            }
+        }
+        into_result(errors)
+    }
+}
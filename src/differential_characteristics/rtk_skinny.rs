@@ -0,0 +1,40 @@
+use serde::{Deserialize, Serialize};
+
+use crate::differential_characteristics::probability::{TheoreticalProbability, theoretical_probability};
+use crate::differential_characteristics::validation::{ValidationError, check_linear_layer, check_states, check_tweakey, check_tweakey_schedule, into_result, next_skinny_tweakey};
+use crate::sbox::Sbox;
+
+#[derive(Serialize, Deserialize)]
+pub struct RelatedTweakeySkinnyDifferentialCharacteristic {
+    #[serde(rename="X")]
+    pub x: Vec<Vec<Vec<u8>>>,
+    #[serde(rename="SC")]
+    pub sc: Vec<Vec<Vec<u8>>>,
+    #[serde(rename="TK")]
+    pub tk: Vec<Vec<Vec<u8>>>,
+    pub objective: usize
+}
+
+impl RelatedTweakeySkinnyDifferentialCharacteristic {
+    pub fn theoretical_probability(&self, sbox: &Sbox) -> TheoreticalProbability {
+        theoretical_probability(&self.x, &self.sc, sbox)
+    }
+
+    /// Checks the structure of the characteristic, `bits` being 4 for SKINNY-64 and 8 for SKINNY-128.
+    pub fn validate(&self, bits: usize) -> Result<(), Vec<ValidationError>> {
+        into_result(self.check("", bits))
+    }
+
+    pub(crate) fn check(&self, prefix: &str, bits: usize) -> Vec<ValidationError> {
+        let mut errors = vec![];
+        let rounds = self.x.len().saturating_sub(1);
+        let valid = check_states(&mut errors, &format!("{}X", prefix), &self.x, 1, bits)
+            & check_states(&mut errors, &format!("{}SC", prefix), &self.sc, rounds, bits)
+            & check_tweakey(&mut errors, &format!("{}TK", prefix), &self.tk, 1..=3, rounds.max(1), bits);
+        if valid {
+            check_linear_layer(&mut errors, prefix, &self.x, &self.sc, Some(&self.tk));
+            check_tweakey_schedule(&mut errors, prefix, &self.tk, |lanes| next_skinny_tweakey(bits, lanes));
+        }
+        errors
+    }
+}
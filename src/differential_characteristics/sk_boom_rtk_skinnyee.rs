@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 use crate::differential_characteristics::sk_rtk_skinnyee::SingleKeyRelatedTweakeySkinnyEEDifferentialCharacteristic;
+use crate::differential_characteristics::validation::{ValidationError, check_boomerang_rounds, check_rounds, into_result};
 
 #[derive(Serialize, Deserialize)]
 pub struct SingleKeyRelatedTweakeySkinnyEEBoomerangCharacteristic {
@@ -10,4 +11,23 @@ pub struct SingleKeyRelatedTweakeySkinnyEEBoomerangCharacteristic {
     pub r0: usize,
     pub rm: usize,
     pub r1: usize,
-}
\ No newline at end of file
+}
+
+impl SingleKeyRelatedTweakeySkinnyEEBoomerangCharacteristic {
+    /// Checks both characteristics and that `r0`, `rm` and `r1` fit them.
+    pub fn validate(&self) -> Result<(), Vec<ValidationError>> {
+        let mut errors = self.e0_em.check("E0EM.");
+        errors.extend(self.em_e1.check("EME1."));
+        if self.e0_em.x.is_empty() || self.em_e1.x.is_empty() {
+            return into_result(errors);
+        }
+        let valid = check_boomerang_rounds(&mut errors, self.r0, self.rm, self.r1, self.e0_em.x.len() - 1, self.em_e1.x.len() - 1);
+        if valid {
+            // The middle rounds start at round r0 - rm of the first characteristic
+            for (z, lane) in self.e0_em.tk.iter().enumerate() {
+                check_rounds(&mut errors, || format!("E0EM.TK[{}]", z), self.r0 - self.rm + 1, lane.len());
+            }
+        }
+        into_result(errors)
+    }
+}
@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+
+use crate::differential_characteristics::validation::{ValidationError, check_nibble_states, into_result};
+
+/// Nibble `i` of a round difference is bits `4i..4i+3` of the state.
+#[derive(Serialize, Deserialize)]
+pub struct SingleKeyGiftDifferentialCharacteristic {
+    #[serde(rename="X")]
+    pub x: Vec<Vec<u8>>,
+    #[serde(rename="SC")]
+    pub sc: Vec<Vec<u8>>,
+    pub objective: usize
+}
+
+impl SingleKeyGiftDifferentialCharacteristic {
+    /// Checks the dimensions of the characteristic, `nibbles` being 16 for GIFT-64 and 32 for GIFT-128.
+    pub fn validate(&self, nibbles: usize) -> Result<(), Vec<ValidationError>> {
+        let mut errors = vec![];
+        let rounds = self.x.len().saturating_sub(1);
+        check_nibble_states(&mut errors, "X", &self.x, 1, nibbles);
+        check_nibble_states(&mut errors, "SC", &self.sc, rounds, nibbles);
+        into_result(errors)
+    }
+}
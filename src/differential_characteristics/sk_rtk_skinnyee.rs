@@ -1,5 +1,11 @@
 use serde::{Deserialize, Serialize};
 
+use crate::differential_characteristics::probability::{TheoreticalProbability, theoretical_probability};
+use crate::differential_characteristics::validation::{ValidationError, check_linear_layer, check_linear_layer_round, check_states, check_tweakey, check_tweakey_schedule, check_tweakey_schedule_round, into_result};
+use crate::matrix::Matrix;
+use crate::sbox::Sbox;
+use crate::skinnyee::SKINNYee;
+
 #[derive(Serialize, Deserialize)]
 pub struct SingleKeyRelatedTweakeySkinnyEEDifferentialCharacteristic {
     #[serde(rename="X")]
@@ -9,4 +15,114 @@ pub struct SingleKeyRelatedTweakeySkinnyEEDifferentialCharacteristic {
     #[serde(rename="TK")]
     pub tk: Vec<Vec<Vec<u8>>>,
     pub objective: usize
-}
\ No newline at end of file
+}
+
+impl SingleKeyRelatedTweakeySkinnyEEDifferentialCharacteristic {
+    pub fn theoretical_probability(&self, sbox: &Sbox) -> TheoreticalProbability {
+        theoretical_probability(&self.x, &self.sc, sbox)
+    }
+
+    /// Checks the structure of the characteristic.
+    pub fn validate(&self) -> Result<(), Vec<ValidationError>> {
+        into_result(self.check(""))
+    }
+
+    pub(crate) fn check(&self, prefix: &str) -> Vec<ValidationError> {
+        let mut errors = vec![];
+        if self.check_structure(&mut errors, prefix) {
+            // The key rows only add constants, so the state only sees the tweakey lanes
+            check_linear_layer(&mut errors, prefix, &self.x, &self.sc, Some(&self.tk));
+            check_tweakey_schedule(&mut errors, prefix, &self.tk, next_round_tweakey());
+        }
+        errors
+    }
+
+    /// Runs the checks of `validate` round by round and stops at the first defect: a structural
+    /// one, or else the earliest round whose `X` does not follow from `SC` and the tweakey lanes or
+    /// whose lanes do not follow from the tweakey schedule.
+    pub fn first_tweakey_inconsistency(&self) -> Result<(), ValidationError> {
+        let mut errors = vec![];
+        if self.check_structure(&mut errors, "") {
+            let next = next_round_tweakey();
+            for round in 0..self.x.len() - 1 {
+                check_linear_layer_round(&mut errors, "", &self.x, &self.sc, Some(&self.tk), round);
+                check_tweakey_schedule_round(&mut errors, "", &self.tk, round, &next);
+                if !errors.is_empty() {
+                    break;
+                }
+            }
+        }
+        errors.into_iter().next().map_or(Ok(()), Err)
+    }
+
+    fn check_structure(&self, errors: &mut Vec<ValidationError>, prefix: &str) -> bool {
+        let rounds = self.x.len().saturating_sub(1);
+        check_states(errors, &format!("{}X", prefix), &self.x, 1, 4)
+            & check_states(errors, &format!("{}SC", prefix), &self.sc, rounds, 4)
+            & check_tweakey(errors, &format!("{}TK", prefix), &self.tk, 4..=4, rounds.max(1), 4)
+    }
+}
+
+/// One round of the tweakey schedule on the four lanes, one per row.
+fn next_round_tweakey() -> impl Fn(&Matrix<u8>) -> Matrix<u8> {
+    let skinnyee = SKINNYee::default();
+    move |lanes| {
+        let round_tweakey = skinnyee.nr_tweak_key_schedule(lanes, 1).swap_remove(1);
+        Matrix::new(4, 16, round_tweakey[1..].iter().flat_map(|lane| lane.values.clone()).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::differential_characteristics::sk_rtk_skinnyee::SingleKeyRelatedTweakeySkinnyEEDifferentialCharacteristic;
+    use crate::differential_characteristics::validation::ValidationError;
+    use crate::matrix::Matrix;
+    use crate::skinnyee::SKINNYee;
+
+    /// Three rounds where `SC` cancels the tweakey difference so that `X` stays null, only the
+    /// structure matters here.
+    fn characteristic() -> SingleKeyRelatedTweakeySkinnyEEDifferentialCharacteristic {
+        let skinnyee = SKINNYee::default();
+        let mut lanes = vec![0; 64];
+        lanes[0] = 0x1;
+        lanes[16] = 0x1;
+        let round_tweakeys = skinnyee.nr_tweak_key_schedule(&Matrix::new(4, 16, lanes), 3);
+        let tk = (1..=4)
+            .map(|z| round_tweakeys.iter().map(|it| it[z].values.clone()).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let mut sc = vec![vec![vec![0; 4]; 4]; 3];
+        for round in 0..3 {
+            for i in 0..8 {
+                sc[round][i / 4][i % 4] = (0..4).fold(0, |acc, z| acc ^ tk[z][round][i]);
+            }
+        }
+        SingleKeyRelatedTweakeySkinnyEEDifferentialCharacteristic { x: vec![vec![vec![0; 4]; 4]; 4], sc, tk, objective: 0 }
+    }
+
+    #[test]
+    fn test_consistent_tweakey() {
+        let dc = characteristic();
+        assert_eq!(dc.first_tweakey_inconsistency(), Ok(()));
+        assert_eq!(dc.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_inconsistent_tweakey() {
+        let mut dc = characteristic();
+        let expected = dc.tk[2][2][5];
+        dc.tk[2][2][5] ^= 0x3;
+        dc.tk[3][3][0] ^= 0x1;
+        let field = "TK[2][2][5]".to_string();
+        assert_eq!(dc.first_tweakey_inconsistency(), Err(ValidationError::TweakeySchedule { field, expected, found: expected ^ 0x3 }));
+
+        let mut dc = characteristic();
+        dc.sc[1][0][3] ^= 0x4;
+        let field = "X[2][0][3]".to_string();
+        assert_eq!(dc.first_tweakey_inconsistency(), Err(ValidationError::LinearLayer { field, expected: 0x4, found: 0 }));
+
+        let mut dc = characteristic();
+        dc.tk[1].truncate(1);
+        dc.sc[0][1].pop();
+        assert!(matches!(dc.first_tweakey_inconsistency(), Err(ValidationError::Dimension { .. } | ValidationError::MissingRounds { .. })));
+    }
+}
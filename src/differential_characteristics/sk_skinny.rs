@@ -1,5 +1,9 @@
 use serde::{Deserialize, Serialize};
 
+use crate::differential_characteristics::probability::{TheoreticalProbability, theoretical_probability};
+use crate::differential_characteristics::validation::{ValidationError, check_linear_layer, check_states, into_result};
+use crate::sbox::Sbox;
+
 #[derive(Serialize, Deserialize)]
 pub struct SingleKeySkinnyDifferentialCharacteristic {
     #[serde(rename="X")]
@@ -7,4 +11,22 @@ pub struct SingleKeySkinnyDifferentialCharacteristic {
     #[serde(rename="SC")]
     pub sc: Vec<Vec<Vec<u8>>>,
     pub objective: usize
-}
\ No newline at end of file
+}
+
+impl SingleKeySkinnyDifferentialCharacteristic {
+    pub fn theoretical_probability(&self, sbox: &Sbox) -> TheoreticalProbability {
+        theoretical_probability(&self.x, &self.sc, sbox)
+    }
+
+    /// Checks the structure of the characteristic, `bits` being 4 for SKINNY-64 and 8 for SKINNY-128.
+    pub fn validate(&self, bits: usize) -> Result<(), Vec<ValidationError>> {
+        let mut errors = vec![];
+        let rounds = self.x.len().saturating_sub(1);
+        let valid = check_states(&mut errors, "X", &self.x, 1, bits)
+            & check_states(&mut errors, "SC", &self.sc, rounds, bits);
+        if valid {
+            check_linear_layer(&mut errors, "", &self.x, &self.sc, None);
+        }
+        into_result(errors)
+    }
+}
@@ -0,0 +1,319 @@
+use std::fmt;
+use std::io;
+use std::ops::RangeInclusive;
+
+use crate::matrix::Matrix;
+use crate::ciphers::skinny::SKINNY;
+
+/// Structural defect of a characteristic file, fields are named after their JSON keys.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ValidationError {
+    /// `field` does not have the expected number of entries.
+    Dimension { field: String, expected: usize, found: usize },
+    /// `field` has fewer rounds than the characteristic needs.
+    MissingRounds { field: String, expected: usize, found: usize },
+    /// `field` does not have an allowed number of tweakey lanes.
+    LaneCount { field: String, allowed: RangeInclusive<usize>, found: usize },
+    /// Cell `field` does not fit in the cell size of the cipher.
+    CellRange { field: String, bits: usize, value: u8 },
+    /// Cell `field` of a state differs from the image of the previous `SC` (and round tweakey)
+    /// through ShiftRows and MixColumns.
+    LinearLayer { field: String, expected: u8, found: u8 },
+    /// Cell `field` of a tweakey lane differs from the image of the previous round through the
+    /// tweakey schedule.
+    TweakeySchedule { field: String, expected: u8, found: u8 },
+    /// `r0`, `rm` and `r1` do not fit the characteristics of a boomerang.
+    BoomerangRounds { r0: usize, rm: usize, r1: usize, reason: &'static str },
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidationError::Dimension { field, expected, found } =>
+                write!(f, "{} has {} entries, {} expected", field, found, expected),
+            ValidationError::MissingRounds { field, expected, found } =>
+                write!(f, "{} has {} rounds, at least {} expected", field, found, expected),
+            ValidationError::LaneCount { field, allowed, found } =>
+                write!(f, "{} has {} lanes, {} to {} expected", field, found, allowed.start(), allowed.end()),
+            ValidationError::CellRange { field, bits, value } =>
+                write!(f, "{} = {:#x} does not fit in {} bits", field, value, bits),
+            ValidationError::LinearLayer { field, expected, found } =>
+                write!(f, "{} = {:#x}, the linear layer gives {:#x}", field, found, expected),
+            ValidationError::TweakeySchedule { field, expected, found } =>
+                write!(f, "{} = {:#x}, the tweakey schedule gives {:#x}", field, found, expected),
+            ValidationError::BoomerangRounds { r0, rm, r1, reason } =>
+                write!(f, "r0 = {}, rm = {}, r1 = {}: {}", r0, rm, r1, reason),
+        }
+    }
+}
+
+/// Single error listing every defect, for binaries returning `io::Result`.
+pub fn to_io_error(errors: Vec<ValidationError>) -> io::Error {
+    let message = errors.iter().map(|it| it.to_string()).collect::<Vec<_>>().join("\n");
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+pub(crate) fn into_result(errors: Vec<ValidationError>) -> Result<(), Vec<ValidationError>> {
+    if errors.is_empty() { Ok(()) } else { Err(errors) }
+}
+
+pub(crate) fn check_cell(errors: &mut Vec<ValidationError>, field: impl FnOnce() -> String, value: u8, bits: usize) {
+    if bits < 8 && value >> bits != 0 {
+        errors.push(ValidationError::CellRange { field: field(), bits, value });
+    }
+}
+
+pub(crate) fn check_len(errors: &mut Vec<ValidationError>, field: impl FnOnce() -> String, expected: usize, found: usize) -> bool {
+    if expected != found {
+        errors.push(ValidationError::Dimension { field: field(), expected, found });
+    }
+    expected == found
+}
+
+pub(crate) fn check_rounds(errors: &mut Vec<ValidationError>, field: impl FnOnce() -> String, expected: usize, found: usize) -> bool {
+    if found < expected {
+        errors.push(ValidationError::MissingRounds { field: field(), expected, found });
+    }
+    found >= expected
+}
+
+/// At least `rounds` 4x4 states of `bits`-bit cells.
+pub(crate) fn check_states(errors: &mut Vec<ValidationError>, field: &str, states: &[Vec<Vec<u8>>], rounds: usize, bits: usize) -> bool {
+    let mut valid = check_rounds(errors, || field.to_string(), rounds, states.len());
+    for (round, state) in states.iter().enumerate() {
+        valid &= check_len(errors, || format!("{}[{}]", field, round), 4, state.len());
+        for (row, cells) in state.iter().enumerate() {
+            valid &= check_len(errors, || format!("{}[{}][{}]", field, round, row), 4, cells.len());
+            for (col, &value) in cells.iter().enumerate() {
+                check_cell(errors, || format!("{}[{}][{}][{}]", field, round, row, col), value, bits);
+            }
+        }
+    }
+    valid
+}
+
+/// At least `rounds` states of `nibbles` nibbles, as used by GIFT.
+pub(crate) fn check_nibble_states(errors: &mut Vec<ValidationError>, field: &str, states: &[Vec<u8>], rounds: usize, nibbles: usize) -> bool {
+    let mut valid = check_rounds(errors, || field.to_string(), rounds, states.len());
+    for (round, state) in states.iter().enumerate() {
+        valid &= check_len(errors, || format!("{}[{}]", field, round), nibbles, state.len());
+        for (i, &value) in state.iter().enumerate() {
+            check_cell(errors, || format!("{}[{}][{}]", field, round, i), value, 4);
+        }
+    }
+    valid
+}
+
+/// `lanes` tweakey lanes of at least `rounds` rounds of 16 `bits`-bit cells.
+pub(crate) fn check_tweakey(errors: &mut Vec<ValidationError>, field: &str, tk: &[Vec<Vec<u8>>], lanes: RangeInclusive<usize>, rounds: usize, bits: usize) -> bool {
+    let mut valid = lanes.contains(&tk.len());
+    if !valid {
+        errors.push(ValidationError::LaneCount { field: field.to_string(), allowed: lanes, found: tk.len() });
+    }
+    for (z, lane) in tk.iter().enumerate() {
+        valid &= check_rounds(errors, || format!("{}[{}]", field, z), rounds, lane.len());
+        for (round, cells) in lane.iter().enumerate() {
+            valid &= check_len(errors, || format!("{}[{}][{}]", field, z, round), 16, cells.len());
+            for (i, &value) in cells.iter().enumerate() {
+                check_cell(errors, || format!("{}[{}][{}][{}]", field, z, round, i), value, bits);
+            }
+        }
+    }
+    valid
+}
+
+/// `X[r + 1] = MC(SR(SC[r] ^ STK[r]))` for the `x.len() - 1` rounds, `STK[r]` being the two first
+/// rows of the XOR of the tweakey lanes at round `r`. States must have been checked beforehand.
+pub(crate) fn check_linear_layer(errors: &mut Vec<ValidationError>, prefix: &str, x: &[Vec<Vec<u8>>], sc: &[Vec<Vec<u8>>], tk: Option<&[Vec<Vec<u8>>]>) {
+    for round in 0..x.len() - 1 {
+        check_linear_layer_round(errors, prefix, x, sc, tk, round);
+    }
+}
+
+/// `check_linear_layer` for round `round` only.
+pub(crate) fn check_linear_layer_round(errors: &mut Vec<ValidationError>, prefix: &str, x: &[Vec<Vec<u8>>], sc: &[Vec<Vec<u8>>], tk: Option<&[Vec<Vec<u8>>]>, round: usize) {
+    // The linear layer is the same for every SKINNY variant
+    let skinny = SKINNY::v64();
+    let mut state = Matrix::new(4, 4, sc[round].concat());
+    for lane in tk.unwrap_or_default() {
+        for (cell, &difference) in state.values.iter_mut().zip(&lane[round]).take(8) {
+            *cell ^= difference;
+        }
+    }
+    skinny.shift_rows(&mut state);
+    skinny.mix_columns(&mut state);
+    for row in 0..4 {
+        for col in 0..4 {
+            let (expected, found) = (state[(row, col)], x[round + 1][row][col]);
+            if expected != found {
+                let field = format!("{}X[{}][{}][{}]", prefix, round + 1, row, col);
+                errors.push(ValidationError::LinearLayer { field, expected, found });
+            }
+        }
+    }
+}
+
+/// Runs `next`, which maps the `tk x 16` lanes of a round to the next round, on every round given
+/// by all the lanes of `tk`. Lanes must have been checked beforehand.
+pub(crate) fn check_tweakey_schedule(errors: &mut Vec<ValidationError>, prefix: &str, tk: &[Vec<Vec<u8>>], next: impl Fn(&Matrix<u8>) -> Matrix<u8>) {
+    let rounds = tk.iter().map(Vec::len).min().unwrap_or(0);
+    for round in 0..rounds.saturating_sub(1) {
+        check_tweakey_schedule_round(errors, prefix, tk, round, &next);
+    }
+}
+
+/// `check_tweakey_schedule` from round `round` to round `round + 1` only, nothing is checked if
+/// a lane stops before.
+pub(crate) fn check_tweakey_schedule_round(errors: &mut Vec<ValidationError>, prefix: &str, tk: &[Vec<Vec<u8>>], round: usize, next: &impl Fn(&Matrix<u8>) -> Matrix<u8>) {
+    if tk.iter().any(|lane| lane.len() <= round + 1) {
+        return;
+    }
+    let lanes = tk.iter().flat_map(|lane| lane[round].iter().cloned()).collect::<Vec<_>>();
+    let next_round = next(&Matrix::new(tk.len(), 16, lanes));
+    for (z, lane) in tk.iter().enumerate() {
+        for (i, (&expected, &found)) in next_round.values[16 * z..].iter().zip(&lane[round + 1]).enumerate() {
+            if expected != found {
+                let field = format!("{}TK[{}][{}][{}]", prefix, z, round + 1, i);
+                errors.push(ValidationError::TweakeySchedule { field, expected, found });
+            }
+        }
+    }
+}
+
+/// `E0` covers `r0` rounds of the first characteristic and `E1` `r1` rounds of the second one, the
+/// `rm` last rounds of `E0` being the `rm` first rounds of `E1`.
+pub(crate) fn check_boomerang_rounds(errors: &mut Vec<ValidationError>, r0: usize, rm: usize, r1: usize, e0_em_rounds: usize, em_e1_rounds: usize) -> bool {
+    let mut reasons = vec![];
+    if rm > r0 || rm > r1 {
+        reasons.push("rm exceeds r0 or r1");
+    }
+    if r0 > e0_em_rounds {
+        reasons.push("E0EM has fewer than r0 rounds");
+    }
+    if r1 > em_e1_rounds {
+        reasons.push("EME1 has fewer than r1 rounds");
+    }
+    for &reason in &reasons {
+        errors.push(ValidationError::BoomerangRounds { r0, rm, r1, reason });
+    }
+    reasons.is_empty()
+}
+
+/// One round of the SKINNY tweakey schedule on `tk x 16` lanes of `bits`-bit cells.
+pub(crate) fn next_skinny_tweakey(bits: usize, lanes: &Matrix<u8>) -> Matrix<u8> {
+    let skinny = match bits {
+        4 => SKINNY::v64_with_rounds(1),
+        8 => SKINNY::v128_with_rounds(1),
+        _ => panic!("SKINNY cells have 4 or 8 bits, got {}", bits),
+    };
+    let tk = lanes.values.len() / 16;
+    let round_tweakey = skinny.key_schedule(lanes, tk).swap_remove(1);
+    Matrix::new(tk, 16, round_tweakey[1..].iter().flat_map(|lane| lane.values.clone()).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::differential_characteristics::rtk_boom_skinny::RelatedTweakeySkinnyBoomerangCharacteristic;
+    use crate::differential_characteristics::rtk_skinny::RelatedTweakeySkinnyDifferentialCharacteristic;
+    use crate::differential_characteristics::sk_skinny::SingleKeySkinnyDifferentialCharacteristic;
+    use crate::differential_characteristics::validation::ValidationError;
+    use crate::matrix::Matrix;
+    use crate::skinny::SKINNY;
+
+    fn zero_states(rounds: usize) -> Vec<Vec<Vec<u8>>> {
+        vec![vec![vec![0; 4]; 4]; rounds]
+    }
+
+    fn linear_layer(sc: &[Vec<u8>]) -> Vec<Vec<u8>> {
+        let skinny = SKINNY::v64();
+        let mut state = Matrix::new(4, 4, sc.concat());
+        skinny.shift_rows(&mut state);
+        skinny.mix_columns(&mut state);
+        state.values.chunks(4).map(|row| row.to_vec()).collect()
+    }
+
+    #[test]
+    fn test_valid_single_key_characteristic() {
+        let mut x = zero_states(3);
+        let mut sc = zero_states(2);
+        x[0][0][0] = 0x1;
+        sc[0][0][0] = 0x8;
+        x[1] = linear_layer(&sc[0]);
+        sc[1] = x[1].clone();
+        x[2] = linear_layer(&sc[1]);
+        let dc = SingleKeySkinnyDifferentialCharacteristic { x, sc, objective: 4 };
+        assert_eq!(dc.validate(4), Ok(()));
+    }
+
+    #[test]
+    fn test_malformed_single_key_characteristic() {
+        let mut x = zero_states(3);
+        x[1][2].pop();
+        x[2][3][1] = 0x10;
+        let dc = SingleKeySkinnyDifferentialCharacteristic { x, sc: zero_states(1), objective: 0 };
+        let errors = dc.validate(4).unwrap_err();
+        assert_eq!(errors, vec![
+            ValidationError::Dimension { field: "X[1][2]".to_string(), expected: 4, found: 3 },
+            ValidationError::CellRange { field: "X[2][3][1]".to_string(), bits: 4, value: 0x10 },
+            ValidationError::MissingRounds { field: "SC".to_string(), expected: 2, found: 1 },
+        ]);
+        assert_eq!(errors[1].to_string(), "X[2][3][1] = 0x10 does not fit in 4 bits");
+        // 8-bit cells are in range, the linear layer is checked once dimensions are right
+        let mut x = zero_states(2);
+        x[1][0][0] = 0x10;
+        let dc = SingleKeySkinnyDifferentialCharacteristic { x, sc: zero_states(1), objective: 0 };
+        assert_eq!(dc.validate(8), Err(vec![
+            ValidationError::LinearLayer { field: "X[1][0][0]".to_string(), expected: 0, found: 0x10 },
+        ]));
+    }
+
+    #[test]
+    fn test_tweakey_schedule() {
+        let skinny = SKINNY::v64_with_rounds(2);
+        let mut tk = vec![0; 32];
+        tk[0] = 0x3;
+        tk[16] = 0x3;
+        let round_tweakeys = skinny.key_schedule(&Matrix::new(2, 16, tk), 2);
+        // TK[z][r] for the three rounds
+        let mut lanes = (1..=2)
+            .map(|z| round_tweakeys.iter().map(|it| it[z].values.clone()).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        // TK1 ^ TK2 cancels in the first round, the state difference stays null
+        let dc = RelatedTweakeySkinnyDifferentialCharacteristic {
+            x: zero_states(2),
+            sc: zero_states(1),
+            tk: lanes.clone(),
+            objective: 0,
+        };
+        assert_eq!(dc.validate(4), Ok(()));
+
+        lanes[1][1][7] ^= 0x1;
+        let dc = RelatedTweakeySkinnyDifferentialCharacteristic { x: zero_states(3), sc: zero_states(2), tk: lanes, objective: 0 };
+        let errors = dc.validate(4).unwrap_err();
+        assert!(errors.contains(&ValidationError::TweakeySchedule { field: "TK[1][1][7]".to_string(), expected: round_tweakeys[1][2].values[7], found: round_tweakeys[1][2].values[7] ^ 0x1 }));
+        assert!(errors.iter().any(|it| matches!(it, ValidationError::LinearLayer { .. })));
+    }
+
+    #[test]
+    fn test_boomerang_rounds() {
+        let characteristic = |rounds: usize| RelatedTweakeySkinnyDifferentialCharacteristic {
+            x: zero_states(rounds + 1),
+            sc: zero_states(rounds),
+            tk: vec![vec![vec![0; 16]; rounds + 1]; 2],
+            objective: 0,
+        };
+        let mut dc = RelatedTweakeySkinnyBoomerangCharacteristic { e0_em: characteristic(3), em_e1: characteristic(2), r0: 3, rm: 1, r1: 2 };
+        assert_eq!(dc.validate(8), Ok(()));
+        (dc.rm, dc.r1) = (3, 4);
+        assert_eq!(dc.validate(8), Err(vec![
+            ValidationError::BoomerangRounds { r0: 3, rm: 3, r1: 4, reason: "EME1 has fewer than r1 rounds" },
+        ]));
+        (dc.rm, dc.r1) = (0, 2);
+        dc.e0_em.tk[1].pop();
+        dc.e0_em.sc[0][1][2] = 0x1;
+        assert_eq!(dc.validate(8), Err(vec![
+            ValidationError::LinearLayer { field: "E0EM.X[1][2][3]".to_string(), expected: 0x1, found: 0 },
+            ValidationError::MissingRounds { field: "E0EM.TK[1]".to_string(), expected: 4, found: 3 },
+        ]));
+    }
+}
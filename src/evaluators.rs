@@ -0,0 +1,268 @@
+use std::ops::{BitXor, BitXorAssign};
+
+use crate::ciphers::bitsliced::{BitslicedBatch, BitsliceWord};
+use crate::ciphers::{ExpandedBlockCipher, RoundByRoundBlockCipher};
+use crate::differential_characteristics::probability::TheoreticalProbability;
+use crate::matrix::Matrix;
+
+// Cipher-agnostic evaluators: any expanded cipher whose blocks support XOR can be plugged in.
+
+pub fn evaluate_boomerang<B, E>(
+    key_and_tweakey: &E,
+    mut p0: B,
+    e0_input_difference: &B,
+    e1_output_difference: &B,
+    tk_xor_tke0: &E,
+    tk_xor_tke1: &E,
+    tk_xor_tke0_xor_tke1: &E,
+) ->  usize
+where
+    E: ExpandedBlockCipher<B>,
+    B: PartialEq + for<'a> BitXorAssign<&'a B>,
+    for<'a> &'a B: BitXor<&'a B, Output = B>,
+{
+    let mut p1 = &p0 ^ e0_input_difference;
+
+    key_and_tweakey.encrypt_block(&mut p0);
+    p0 ^= e1_output_difference;
+    tk_xor_tke1.decrypt_block(&mut p0);
+
+    tk_xor_tke0.encrypt_block(&mut p1);
+    p1 ^= e1_output_difference;
+    tk_xor_tke0_xor_tke1.decrypt_block(&mut p1);
+
+    let d_out = &p0 ^ &p1;
+    if e0_input_difference == &d_out {
+        1usize
+    } else {
+        0usize
+    }
+}
+
+pub fn evaluate_differential_characteristic<B, E>(
+    key_and_tweakey: &E,
+    mut p0: B,
+    input_difference: &B,
+    output_difference: &B,
+    key_and_tweakey_xor_tweakey_difference: &E,
+) -> usize
+where
+    E: ExpandedBlockCipher<B>,
+    B: PartialEq,
+    for<'a> &'a B: BitXor<&'a B, Output = B>,
+{
+    let mut p1 = &p0 ^ input_difference;
+    key_and_tweakey.encrypt_block(&mut p0);
+    key_and_tweakey_xor_tweakey_difference.encrypt_block(&mut p1);
+
+    let d_out = &p0 ^ &p1;
+    if output_difference == &d_out {
+        1
+    } else {
+        0
+    }
+}
+
+/// Bitsliced `evaluate_boomerang`: returns the mask of the lanes of `p0` for which the quartet returns.
+pub fn evaluate_boomerang_batch<W, E>(
+    key_and_tweakey: &E,
+    mut p0: BitslicedBatch<W>,
+    e0_input_difference: u64,
+    e1_output_difference: u64,
+    tk_xor_tke0: &E,
+    tk_xor_tke1: &E,
+    tk_xor_tke0_xor_tke1: &E,
+) -> W
+where
+    W: BitsliceWord,
+    E: ExpandedBlockCipher<BitslicedBatch<W>>,
+{
+    let mut p1 = p0.clone();
+    p1.xor_packed(e0_input_difference);
+
+    key_and_tweakey.encrypt_block(&mut p0);
+    p0.xor_packed(e1_output_difference);
+    tk_xor_tke1.decrypt_block(&mut p0);
+
+    tk_xor_tke0.encrypt_block(&mut p1);
+    p1.xor_packed(e1_output_difference);
+    tk_xor_tke0_xor_tke1.decrypt_block(&mut p1);
+
+    p0.lanes_with_difference(&p1, e0_input_difference)
+}
+
+/// Bitsliced `evaluate_differential_characteristic`: returns the mask of the lanes of `p0` following the characteristic.
+pub fn evaluate_differential_characteristic_batch<W, E>(
+    key_and_tweakey: &E,
+    mut p0: BitslicedBatch<W>,
+    input_difference: u64,
+    output_difference: u64,
+    key_and_tweakey_xor_tweakey_difference: &E,
+) -> W
+where
+    W: BitsliceWord,
+    E: ExpandedBlockCipher<BitslicedBatch<W>>,
+{
+    let mut p1 = p0.clone();
+    p1.xor_packed(input_difference);
+    key_and_tweakey.encrypt_block(&mut p0);
+    key_and_tweakey_xor_tweakey_difference.encrypt_block(&mut p1);
+
+    p0.lanes_with_difference(&p1, output_difference)
+}
+
+/// Number of rounds of the characteristic followed by the pair `(p0, p0 ^ x[0])`: round `r` is
+/// followed when the differences after its S-box layer and at its output are `sc[r]` and `x[r + 1]`.
+pub fn count_followed_rounds<B, E>(
+    key_and_tweakey: &E,
+    mut p0: B,
+    x: &[B],
+    sc: &[B],
+    key_and_tweakey_xor_tweakey_difference: &E,
+) -> usize
+where
+    E: RoundByRoundBlockCipher<B>,
+    B: PartialEq,
+    for<'a> &'a B: BitXor<&'a B, Output = B>,
+{
+    let mut p1 = &p0 ^ &x[0];
+    let rounds = key_and_tweakey.nb_rounds().min(x.len() - 1);
+    for round in 0..rounds {
+        key_and_tweakey.sub_cells(round, &mut p0);
+        key_and_tweakey_xor_tweakey_difference.sub_cells(round, &mut p1);
+        if &p0 ^ &p1 != sc[round] {
+            return round;
+        }
+        key_and_tweakey.finish_round(round, &mut p0);
+        key_and_tweakey_xor_tweakey_difference.finish_round(round, &mut p1);
+        if &p0 ^ &p1 != x[round + 1] {
+            return round;
+        }
+    }
+    rounds
+}
+
+/// Histogram of the number of rounds followed by pairs, see `count_followed_rounds`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TrailStatistics {
+    pub histogram: Vec<usize>,
+}
+
+impl TrailStatistics {
+    pub fn new(rounds: usize) -> TrailStatistics {
+        TrailStatistics { histogram: vec![0; rounds + 1] }
+    }
+
+    pub fn record(&mut self, followed_rounds: usize) {
+        self.histogram[followed_rounds] += 1;
+    }
+
+    pub fn merge(mut self, other: &TrailStatistics) -> TrailStatistics {
+        self.histogram.iter_mut()
+            .zip(&other.histogram)
+            .for_each(|(it, other)| *it += other);
+        self
+    }
+
+    /// Number of pairs following at least the `rounds` first rounds.
+    pub fn reaching(&self, rounds: usize) -> usize {
+        self.histogram[rounds..].iter().sum()
+    }
+
+    /// `log2` of the probability that a pair follows round `round` once it followed the previous ones.
+    pub fn conditional_log2(&self, round: usize) -> f64 {
+        (self.reaching(round + 1) as f64).log2() - (self.reaching(round) as f64).log2()
+    }
+
+    /// Histogram, then the measured conditional probability of each round next to its DDT prediction.
+    pub fn report(&self, predicted: &TheoreticalProbability) -> String {
+        let mut lines = vec!["Followed rounds".to_string()];
+        for (rounds, pairs) in self.histogram.iter().enumerate() {
+            lines.push(format!("  {:>2} : {}", rounds, pairs));
+        }
+        for round in 0..self.histogram.len() - 1 {
+            lines.push(format!(
+                "Round {:>2} - {}/{} : 2^{{{}}} (predicted 2^{{{}}})",
+                round,
+                self.reaching(round + 1),
+                self.reaching(round),
+                self.conditional_log2(round),
+                predicted.round_weights.get(round).map_or(f64::NAN, |weight| -weight)
+            ));
+        }
+        lines.join("\n")
+    }
+}
+
+/// `X` or `SC` states of a 4x4 characteristic, in the form `count_followed_rounds` takes them.
+pub fn trail_states(states: &[Vec<Vec<u8>>]) -> Vec<Matrix<u8>> {
+    states.iter()
+        .map(|state| Matrix::new(4, 4, state.concat()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ciphers::{ExpandedBlockCipher, RoundByRoundBlockCipher};
+    use crate::differential_characteristics::probability::TheoreticalProbability;
+    use crate::evaluators::{TrailStatistics, count_followed_rounds};
+    use crate::matrix::Matrix;
+    use crate::skinny::SKINNY;
+
+    fn state(cells: &[(usize, usize, u8)]) -> Matrix<u8> {
+        let mut state = Matrix::new(4, 4, vec![0; 16]);
+        cells.iter().for_each(|&(row, col, value)| state[(row, col)] = value);
+        state
+    }
+
+    #[test]
+    fn test_count_followed_rounds() {
+        let skinny = SKINNY::v64_with_rounds(3);
+        let key = skinny.expand(&Matrix::new(1, 16, (0..16).collect()));
+        let p0 = Matrix::new(4, 4, vec![0x5; 16]);
+        let zero = state(&[]);
+        // The null characteristic is followed by every pair
+        assert_eq!(count_followed_rounds(&key, p0.clone(), &vec![zero.clone(); 4], &vec![zero.clone(); 3], &key), 3);
+
+        // Record the actual trail of a pair, then break it after the S-box layer of round 1
+        let (mut q0, mut q1) = (p0.clone(), &p0 ^ &state(&[(0, 0, 0x1)]));
+        let (mut x, mut sc) = (vec![&q0 ^ &q1], vec![]);
+        for round in 0..3 {
+            key.sub_cells(round, &mut q0);
+            key.sub_cells(round, &mut q1);
+            sc.push(&q0 ^ &q1);
+            key.finish_round(round, &mut q0);
+            key.finish_round(round, &mut q1);
+            x.push(&q0 ^ &q1);
+        }
+        let mut c0 = p0.clone();
+        key.encrypt_block(&mut c0);
+        assert_eq!(c0, q0);
+        assert_eq!(count_followed_rounds(&key, p0.clone(), &x, &sc, &key), 3);
+        sc[1] = &sc[1] ^ &state(&[(2, 2, 0x1)]);
+        assert_eq!(count_followed_rounds(&key, p0.clone(), &x, &sc, &key), 1);
+        x[1] = &x[1] ^ &state(&[(3, 0, 0x4)]);
+        assert_eq!(count_followed_rounds(&key, p0, &x, &sc, &key), 0);
+    }
+
+    #[test]
+    fn test_trail_statistics() {
+        let mut statistics = TrailStatistics::new(2);
+        [0, 0, 0, 0, 1, 1, 1, 1].iter().for_each(|&it| statistics.record(it));
+        let statistics = statistics.merge(&TrailStatistics { histogram: vec![4, 0, 4] });
+        assert_eq!(statistics.histogram, vec![8, 4, 4]);
+        assert_eq!(statistics.reaching(0), 16);
+        assert_eq!(statistics.reaching(1), 8);
+        assert_eq!(statistics.conditional_log2(0), -1.0);
+        assert_eq!(statistics.conditional_log2(1), -1.0);
+        let predicted = TheoreticalProbability { round_weights: vec![2.0, 1.0], impossible_transitions: vec![] };
+        assert_eq!(statistics.report(&predicted), [
+            "Followed rounds",
+            "   0 : 8",
+            "   1 : 4",
+            "   2 : 4",
+            "Round  0 - 8/16 : 2^{-1} (predicted 2^{-2})",
+            "Round  1 - 4/8 : 2^{-1} (predicted 2^{-1})",
+        ].join("\n"));
+    }
+}
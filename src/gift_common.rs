@@ -0,0 +1,33 @@
+use rand::RngCore;
+use rand_xoshiro::Xoshiro256StarStar;
+
+/// Block of a GIFT instance (`u64` for GIFT-64, `u128` for GIFT-128).
+pub trait GiftBlock: Sized {
+    /// Nibble `i` goes to bits `4i..4i+3`.
+    fn from_nibbles(nibbles: &[u8]) -> Self;
+    fn random(rand: &mut Xoshiro256StarStar) -> Self;
+}
+
+impl GiftBlock for u64 {
+    fn from_nibbles(nibbles: &[u8]) -> u64 {
+        assert_eq!(nibbles.len(), 16);
+        u128::from_nibbles(nibbles) as u64
+    }
+
+    fn random(rand: &mut Xoshiro256StarStar) -> u64 {
+        rand.next_u64()
+    }
+}
+
+impl GiftBlock for u128 {
+    fn from_nibbles(nibbles: &[u8]) -> u128 {
+        assert!(nibbles.len() <= 32);
+        nibbles.iter()
+            .enumerate()
+            .fold(0, |acc, (i, nibble)| acc | (*nibble as u128 & 0xF) << (4 * i))
+    }
+
+    fn random(rand: &mut Xoshiro256StarStar) -> u128 {
+        (rand.next_u64() as u128) << 64 | rand.next_u64() as u128
+    }
+}
@@ -0,0 +1 @@
+pub mod skinny_hash;
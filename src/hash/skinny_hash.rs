@@ -0,0 +1,71 @@
+use crate::ciphers::skinny::SKINNY;
+use crate::ciphers::SymmetricCipher;
+use crate::matrix::Matrix;
+
+/// SKINNY-tk3-Hash and SKINNY-tk2-Hash: sponges with a 256-bit digest whose permutation
+/// encrypts the constant blocks `0^120 || i` for `i` = 0, 1 (and 2) under the whole sponge state
+/// used as tweakey.
+pub struct SkinnyHash {
+    skinny: SKINNY,
+    tk: usize,
+    rate: usize,
+}
+
+impl SkinnyHash {
+    /// 384-bit state, 128-bit rate, SKINNY-128-384.
+    pub fn tk3() -> SkinnyHash {
+        SkinnyHash { skinny: SKINNY::v128(), tk: 3, rate: 16 }
+    }
+
+    /// 256-bit state, 32-bit rate, SKINNY-128-256.
+    pub fn tk2() -> SkinnyHash {
+        SkinnyHash { skinny: SKINNY::v128(), tk: 2, rate: 4 }
+    }
+
+    fn permutation(&self, state: &mut [u8]) {
+        let tweakey = Matrix::new(self.tk, 16, state.to_vec());
+        for (i, chunk) in state.chunks_exact_mut(16).enumerate() {
+            let mut block = Matrix::new(4, 4, vec![0; 16]);
+            block[(3, 3)] = i as u8;
+            self.skinny.cipher(&tweakey, &mut block);
+            chunk.copy_from_slice(&block.values);
+        }
+    }
+
+    pub fn hash(&self, message: &[u8]) -> Vec<u8> {
+        let mut state = vec![0; 16 * self.tk];
+        state[self.rate] = 0x80;
+
+        let mut padded = message.to_vec();
+        padded.push(0x80);
+        padded.resize(padded.len().next_multiple_of(self.rate), 0);
+        for block in padded.chunks_exact(self.rate) {
+            state.iter_mut().zip(block).for_each(|(s, m)| *s ^= m);
+            self.permutation(&mut state);
+        }
+
+        let mut digest = Vec::with_capacity(32);
+        digest.extend(&state[..self.rate]);
+        while digest.len() < 32 {
+            self.permutation(&mut state);
+            digest.extend(&state[..self.rate]);
+        }
+        digest
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::hash::skinny_hash::SkinnyHash;
+
+    #[test]
+    fn test_skinny_hash_padding() {
+        for hash in [SkinnyHash::tk2(), SkinnyHash::tk3()] {
+            let empty = hash.hash(&[]);
+            assert_eq!(empty.len(), 32);
+            assert_eq!(hash.hash(&[]), empty);
+            assert_ne!(hash.hash(&[0x80]), empty);
+            assert_ne!(hash.hash(&[0]), hash.hash(&[0, 0]));
+        }
+    }
+}
@@ -0,0 +1,18 @@
+
+pub mod aead;
+pub mod ciphers;
+pub mod hash;
+pub mod matrix;
+pub mod lfsr;
+pub mod sbox;
+pub mod differential_characteristics;
+pub mod evaluators;
+pub mod skinnyee_common;
+pub mod skinnyee_plaintext_generator;
+pub mod skinnyee_boomerang_cli_args;
+pub mod skinny_common;
+pub mod skinny_plaintext_generator;
+pub mod skinny_boomerang_cli_args;
+pub mod gift_common;
+
+pub use crate::ciphers::{aes, craft, deoxys, forkskinny, gift, mantis, skinny, skinnye_v2, skinnyee, Branch, ExpandedBlockCipher, ForkCipher, SymmetricCipher, TweakableBlockCipher};
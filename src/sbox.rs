@@ -0,0 +1,250 @@
+use crate::ciphers::aes::SBOX as AES_SBOX;
+use crate::ciphers::gift::GIFT_SBOX;
+use crate::ciphers::mantis::MIDORI_SBOX;
+use crate::ciphers::skinny::{SKINNY_128_SBOX, SKINNY_64_SBOX};
+
+/// Bijective n-bit S-box with its difference, linear and boomerang tables.
+/// Tables are indexed by the input mask or difference first, then the output one.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Sbox {
+    n: usize,
+    table: Vec<u8>,
+    inverse: Vec<u8>,
+}
+
+impl Sbox {
+    pub fn new(table: &[u8]) -> Sbox {
+        assert!(table.len().is_power_of_two() && table.len() <= 256);
+        let mut inverse = vec![None; table.len()];
+        for (x, y) in table.iter().enumerate() {
+            assert!(inverse[*y as usize].is_none(), "the S-box is not a permutation");
+            inverse[*y as usize] = Some(x as u8);
+        }
+        let inverse = inverse.into_iter().map(Option::unwrap).collect();
+        Sbox { n: table.len().trailing_zeros() as usize, table: table.to_vec(), inverse }
+    }
+
+    pub fn skinny_64() -> Sbox {
+        Sbox::new(&SKINNY_64_SBOX)
+    }
+
+    pub fn skinny_128() -> Sbox {
+        Sbox::new(&SKINNY_128_SBOX)
+    }
+
+    pub fn gift() -> Sbox {
+        Sbox::new(&GIFT_SBOX)
+    }
+
+    /// Sb0 of Midori, used by MANTIS and CRAFT.
+    pub fn midori() -> Sbox {
+        Sbox::new(&MIDORI_SBOX)
+    }
+
+    pub fn aes() -> Sbox {
+        Sbox::new(&AES_SBOX)
+    }
+
+    pub fn inverse(&self) -> Sbox {
+        Sbox { n: self.n, table: self.inverse.clone(), inverse: self.table.clone() }
+    }
+
+    /// Number of input (and output) bits.
+    pub fn bits(&self) -> usize {
+        self.n
+    }
+
+    #[inline]
+    fn size(&self) -> usize {
+        1 << self.n
+    }
+
+    #[inline]
+    pub fn apply(&self, x: usize) -> usize {
+        self.table[x] as usize
+    }
+
+    #[inline]
+    pub fn apply_inverse(&self, y: usize) -> usize {
+        self.inverse[y] as usize
+    }
+
+    /// `S^-1(S(x) ^ nabla_o) ^ S^-1(S(x ^ delta_i) ^ nabla_o) == delta_i`: the boomerang returns
+    /// on this S-box for the pair `(x, x ^ delta_i)`.
+    #[inline]
+    fn returns(&self, x: usize, delta_i: usize, nabla_o: usize) -> bool {
+        self.apply_inverse(self.apply(x) ^ nabla_o) ^ self.apply_inverse(self.apply(x ^ delta_i) ^ nabla_o) == delta_i
+    }
+
+    /// `DDT[a][b] = #{x | S(x) ^ S(x ^ a) = b}`.
+    pub fn ddt(&self) -> Vec<Vec<usize>> {
+        let mut ddt = vec![vec![0; self.size()]; self.size()];
+        for a in 0..self.size() {
+            for x in 0..self.size() {
+                ddt[a][self.apply(x) ^ self.apply(x ^ a)] += 1;
+            }
+        }
+        ddt
+    }
+
+    /// `LAT[a][b] = #{x | a.x = b.S(x)} - 2^(n-1)`.
+    pub fn lat(&self) -> Vec<Vec<i32>> {
+        let half = (self.size() / 2) as i32;
+        (0..self.size()).map(|a| {
+            (0..self.size()).map(|b| {
+                let count = (0..self.size())
+                    .filter(|&x| (a & x).count_ones() % 2 == (b & self.apply(x)).count_ones() % 2)
+                    .count() as i32;
+                count - half
+            }).collect()
+        }).collect()
+    }
+
+    /// `BCT[delta_i][nabla_o] = #{x | S^-1(S(x) ^ nabla_o) ^ S^-1(S(x ^ delta_i) ^ nabla_o) = delta_i}`.
+    pub fn bct(&self) -> Vec<Vec<usize>> {
+        (0..self.size()).map(|delta_i| {
+            (0..self.size()).map(|nabla_o| {
+                (0..self.size()).filter(|&x| self.returns(x, delta_i, nabla_o)).count()
+            }).collect()
+        }).collect()
+    }
+
+    /// Upper BCT for a fixed `delta_i`: `UBCT[delta_o][nabla_o]` counts the `x` of the BCT entry
+    /// `(delta_i, nabla_o)` with `S(x) ^ S(x ^ delta_i) = delta_o`.
+    pub fn ubct(&self, delta_i: usize) -> Vec<Vec<usize>> {
+        let mut ubct = vec![vec![0; self.size()]; self.size()];
+        for x in 0..self.size() {
+            let delta_o = self.apply(x) ^ self.apply(x ^ delta_i);
+            for (nabla_o, count) in ubct[delta_o].iter_mut().enumerate() {
+                if self.returns(x, delta_i, nabla_o) {
+                    *count += 1;
+                }
+            }
+        }
+        ubct
+    }
+
+    /// Lower BCT for a fixed `delta_i`: `LBCT[nabla_i][nabla_o]` counts the `x` of the BCT entry
+    /// `(delta_i, nabla_o)` with `x ^ S^-1(S(x) ^ nabla_o) = nabla_i`.
+    pub fn lbct(&self, delta_i: usize) -> Vec<Vec<usize>> {
+        let mut lbct = vec![vec![0; self.size()]; self.size()];
+        for x in 0..self.size() {
+            for nabla_o in 0..self.size() {
+                if self.returns(x, delta_i, nabla_o) {
+                    lbct[x ^ self.apply_inverse(self.apply(x) ^ nabla_o)][nabla_o] += 1;
+                }
+            }
+        }
+        lbct
+    }
+
+    /// Extended BCT entry: the `x` of the BCT entry `(delta_i, nabla_o)` satisfying both the
+    /// UBCT and the LBCT conditions.
+    pub fn ebct(&self, delta_i: usize, delta_o: usize, nabla_i: usize, nabla_o: usize) -> usize {
+        (0..self.size())
+            .filter(|&x| self.returns(x, delta_i, nabla_o))
+            .filter(|&x| self.apply(x) ^ self.apply(x ^ delta_i) == delta_o)
+            .filter(|&x| x ^ self.apply_inverse(self.apply(x) ^ nabla_o) == nabla_i)
+            .count()
+    }
+
+    /// `DLCT[delta][lambda] = #{x | lambda.(S(x) ^ S(x ^ delta)) = 0} - 2^(n-1)`.
+    pub fn dlct(&self) -> Vec<Vec<i32>> {
+        let half = (self.size() / 2) as i32;
+        (0..self.size()).map(|delta| {
+            (0..self.size()).map(|lambda| {
+                let count = (0..self.size())
+                    .filter(|&x| (lambda & (self.apply(x) ^ self.apply(x ^ delta))).count_ones().is_multiple_of(2))
+                    .count() as i32;
+                count - half
+            }).collect()
+        }).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sbox::Sbox;
+
+    #[test]
+    fn test_ddt_skinny_64() {
+        let ddt = Sbox::skinny_64().ddt();
+        assert_eq!(ddt[0][0], 16);
+        assert!(ddt.iter().all(|row| row.iter().sum::<usize>() == 16));
+        // Differential uniformity 4, as for every optimal 4-bit S-box
+        assert_eq!(ddt.iter().skip(1).flatten().max(), Some(&4));
+        assert_eq!(Sbox::aes().ddt().iter().skip(1).flatten().max(), Some(&4));
+    }
+
+    #[test]
+    fn test_lat() {
+        let sbox = Sbox::skinny_64();
+        let lat = sbox.lat();
+        assert_eq!(lat[0][0], 8);
+        assert!(lat[0].iter().skip(1).all(|&it| it == 0));
+        assert_eq!(lat.iter().skip(1).flatten().map(|it| it.abs()).max(), Some(4));
+        // Parseval: the squared biases of each output mask sum to 2^(2n-2)
+        for b in 0..16 {
+            assert_eq!(lat.iter().map(|row| row[b] * row[b]).sum::<i32>(), 64);
+        }
+        assert_eq!(Sbox::aes().lat().iter().skip(1).flatten().map(|it| it.abs()).max(), Some(16));
+    }
+
+    #[test]
+    fn test_bct() {
+        let sbox = Sbox::skinny_64();
+        let (ddt, bct) = (sbox.ddt(), sbox.bct());
+        for a in 0..16 {
+            assert_eq!(bct[a][0], 16);
+            assert_eq!(bct[0][a], 16);
+            for b in 0..16 {
+                // The BCT dominates the DDT
+                assert!(bct[a][b] >= ddt[a][b]);
+            }
+        }
+        assert_eq!(sbox.inverse().bct()[3][5], bct[5][3]);
+    }
+
+    #[test]
+    fn test_ubct_lbct_ebct() {
+        let sbox = Sbox::gift();
+        let (ddt, bct) = (sbox.ddt(), sbox.bct());
+        for delta_i in 0..16 {
+            let (ubct, lbct) = (sbox.ubct(delta_i), sbox.lbct(delta_i));
+            for nabla_o in 0..16 {
+                assert_eq!((0..16).map(|delta_o| ubct[delta_o][nabla_o]).sum::<usize>(), bct[delta_i][nabla_o]);
+                assert_eq!((0..16).map(|nabla_i| lbct[nabla_i][nabla_o]).sum::<usize>(), bct[delta_i][nabla_o]);
+            }
+            for delta_o in 0..16 {
+                assert_eq!(ubct[delta_o][0], ddt[delta_i][delta_o]);
+            }
+        }
+        let (delta_i, delta_o, nabla_o) = (1, 3, 2);
+        let total = (0..16).map(|nabla_i| sbox.ebct(delta_i, delta_o, nabla_i, nabla_o)).sum::<usize>();
+        assert_eq!(total, sbox.ubct(delta_i)[delta_o][nabla_o]);
+    }
+
+    #[test]
+    fn test_dlct() {
+        let sbox = Sbox::midori();
+        let (ddt, dlct) = (sbox.ddt(), sbox.dlct());
+        assert!(dlct[0].iter().all(|&it| it == 8));
+        assert!((0..16).all(|delta| dlct[delta][0] == 8));
+        // DLCT[delta][lambda] = sum of DDT[delta][b] * (-1)^(lambda.b) / 2
+        for (delta, row) in dlct.iter().enumerate() {
+            for (lambda, &entry) in row.iter().enumerate() {
+                let expected = (0..16).map(|b| {
+                    let sign = if (lambda & b).count_ones().is_multiple_of(2) { 1 } else { -1 };
+                    sign * ddt[delta][b] as i32
+                }).sum::<i32>() / 2;
+                assert_eq!(entry, expected);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_not_a_permutation() {
+        Sbox::new(&[0, 1, 1, 2]);
+    }
+}
@@ -0,0 +1,22 @@
+use std::path::PathBuf;
+use clap::Parser;
+
+#[derive(Copy, Clone, clap::ValueEnum)]
+pub enum Version {
+    V64,
+    V128,
+}
+
+#[derive(Parser)]
+pub struct Args {
+    #[arg(short, long)]
+    pub version: Version,
+    #[arg(short('k'), long, default_value_t = 8)]
+    pub nb_key: usize,
+    #[arg(short, long)]
+    pub nb_tries_per_key: Option<usize>,
+    #[arg(short, long)]
+    pub path: PathBuf,
+    #[arg(short('t'), long, default_value_t=1)]
+    pub nb_threads: usize
+}
@@ -0,0 +1,30 @@
+use rand::RngCore;
+use rand_xoshiro::Xoshiro256StarStar;
+
+use crate::ciphers::skinny::SKINNY;
+use crate::differential_characteristics::rtk_skinny::RelatedTweakeySkinnyDifferentialCharacteristic;
+use crate::matrix::Matrix;
+
+pub fn random_tweakey(rand: &mut Xoshiro256StarStar, tk: usize, mask: u8) -> Matrix<u8> {
+    Matrix::new(tk, 16, (0..16 * tk).map(|_| rand.next_u64() as u8 & mask).collect())
+}
+
+/// Difference of the `tk` tweakey lanes of the characteristic at round `round`, one lane per row.
+pub fn tweakey_difference(dc: &RelatedTweakeySkinnyDifferentialCharacteristic, round: usize) -> Matrix<u8> {
+    let tk = dc.tk.len();
+    assert!(tk == 1 || tk == 2 || tk == 3, "SKINNY uses one to three tweakey lanes, got {}", tk);
+    let difference = dc.tk.iter()
+        .flat_map(|lane| lane[round].iter().cloned())
+        .collect::<Vec<_>>();
+    Matrix::new(tk, 16, difference)
+}
+
+/// Runs the tweakey schedule backward for `nr` rounds on a `tk x 16` tweakey difference.
+pub fn rewind_tweakey_difference(cipher: &SKINNY, difference: &Matrix<u8>, nr: usize) -> Matrix<u8> {
+    let tk = difference.values.len() / 16;
+    let round_tweakey = cipher.inv_key_schedule(difference, tk, nr).pop().unwrap();
+    let difference = round_tweakey[1..=tk].iter()
+        .flat_map(|lane| lane.values.iter().cloned())
+        .collect::<Vec<_>>();
+    Matrix::new(tk, 16, difference)
+}
@@ -0,0 +1,24 @@
+use rand::RngCore;
+use rand_xoshiro::Xoshiro256StarStar;
+use crate::matrix::Matrix;
+
+/// Random 4x4 states whose cells are masked with `mask` (0xF for SKINNY-64, 0xFF for SKINNY-128).
+pub struct SkinnyPlaintextGenerator<'r> {
+    rand: &'r mut Xoshiro256StarStar,
+    mask: u8,
+}
+
+impl <'r> SkinnyPlaintextGenerator<'r> {
+    pub fn new(rand: &'r mut Xoshiro256StarStar, mask: u8) -> SkinnyPlaintextGenerator<'r> {
+        SkinnyPlaintextGenerator { rand, mask }
+    }
+}
+
+impl <'r> Iterator for SkinnyPlaintextGenerator<'r> {
+    type Item = Matrix<u8>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let plaintext = (0..16).map(|_| self.rand.next_u64() as u8 & self.mask).collect();
+        Some(Matrix::new(4, 4, plaintext))
+    }
+}
@@ -1,13 +1,10 @@
 use rand::RngCore;
 use rand_chacha::ChaCha8Rng;
-use crate::ciphers::skinnyee::SKINNYee;
 use crate::matrix::Matrix;
-use crate::ciphers::SymmetricCipher;
 
-#[allow(dead_code)]
 pub fn fill_random_key_and_tweakey(
     rand: &mut ChaCha8Rng,
-    key_and_tweakey: &mut Vec<u8>,
+    key_and_tweakey: &mut [u8],
     mask: u8,
 ) {
     rand.fill_bytes(key_and_tweakey);
@@ -20,7 +17,6 @@ pub fn fill_random_key_and_tweakey(
     key_and_tweakey[24 * 4 + 3] = 0;
 }
 
-#[allow(dead_code)]
 pub fn compute_tk_xor_tweakey_difference(
     key_and_tweakey: &Matrix<u8>,
     tk0_difference: &Matrix<u8>,
@@ -41,53 +37,3 @@ pub fn compute_tk_xor_tweakey_difference(
 
     tk_xor_tke0
 }
-
-#[allow(dead_code)]
-pub fn evaluate_boomerang(
-    cipher: &SKINNYee,
-    key_and_tweakey: &Matrix<u8>,
-    mut p0: Matrix<u8>,
-    e0_input_difference: &Matrix<u8>,
-    e1_output_difference: &Matrix<u8>,
-    tk_xor_tke0: &Matrix<u8>,
-    tk_xor_tke1: &Matrix<u8>,
-    tk_xor_tke0_xor_tke1: &Matrix<u8>,
-) ->  usize {
-    let mut p1 = &p0 ^ e0_input_difference;
-
-    cipher.cipher(key_and_tweakey, &mut p0);
-    p0 ^= e1_output_difference;
-    cipher.decipher(tk_xor_tke1, &mut p0);
-
-    cipher.cipher(tk_xor_tke0, &mut p1);
-    p1 ^= e1_output_difference;
-    cipher.decipher(tk_xor_tke0_xor_tke1, &mut p1);
-
-    let d_out = &p0 ^ &p1;
-    if e0_input_difference == &d_out {
-        1usize
-    } else {
-        0usize
-    }
-}
-
-#[allow(dead_code)]
-pub fn evaluate_differential_characteristic(
-    cipher: &SKINNYee,
-    key_and_tweakey: &Matrix<u8>,
-    mut p0: Matrix<u8>,
-    input_difference: &Matrix<u8>,
-    output_difference: &Matrix<u8>,
-    key_and_tweakey_xor_tweakey_difference: &Matrix<u8>,
-) -> usize {
-    let mut p1 = &p0 ^ input_difference;
-    cipher.cipher(key_and_tweakey, &mut p0);
-    cipher.cipher(key_and_tweakey_xor_tweakey_difference, &mut p1);
-
-    let d_out = &p0 ^ &p1;
-    if output_difference == &d_out {
-        1
-    } else {
-        0
-    }
-}
\ No newline at end of file
@@ -1,5 +1,8 @@
+use std::marker::PhantomData;
+
 use rand::RngCore;
 use rand_chacha::ChaCha8Rng;
+use crate::ciphers::bitsliced::{BitslicedBatch, BitsliceWord};
 use crate::matrix::Matrix;
 
 pub struct SkinnyeePlaintextGenerator<'r> {
@@ -23,4 +26,44 @@ impl <'r> Iterator for SkinnyeePlaintextGenerator<'r> {
         }
         Some(Matrix::new(4, 4, plaintext))
     }
-}
\ No newline at end of file
+}
+
+/// Same plaintexts as `SkinnyeePlaintextGenerator`, in the packed `u64` representation.
+pub struct SkinnyeePackedPlaintextGenerator<'r> {
+    rand: &'r mut ChaCha8Rng
+}
+
+impl <'r> SkinnyeePackedPlaintextGenerator<'r> {
+    pub fn new(rand: &'r mut ChaCha8Rng) -> SkinnyeePackedPlaintextGenerator<'r> {
+        SkinnyeePackedPlaintextGenerator { rand }
+    }
+}
+
+impl <'r> Iterator for SkinnyeePackedPlaintextGenerator<'r> {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.rand.next_u64())
+    }
+}
+
+/// Same plaintexts as `SkinnyeePackedPlaintextGenerator`, grouped in bitsliced batches of `W::LANES` blocks.
+pub struct SkinnyeeBitslicedPlaintextGenerator<'r, W: BitsliceWord> {
+    rand: &'r mut ChaCha8Rng,
+    word: PhantomData<W>,
+}
+
+impl <'r, W: BitsliceWord> SkinnyeeBitslicedPlaintextGenerator<'r, W> {
+    pub fn new(rand: &'r mut ChaCha8Rng) -> SkinnyeeBitslicedPlaintextGenerator<'r, W> {
+        SkinnyeeBitslicedPlaintextGenerator { rand, word: PhantomData }
+    }
+}
+
+impl <'r, W: BitsliceWord> Iterator for SkinnyeeBitslicedPlaintextGenerator<'r, W> {
+    type Item = BitslicedBatch<W>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let plaintexts = (0..W::LANES).map(|_| self.rand.next_u64()).collect::<Vec<_>>();
+        Some(BitslicedBatch::new(&plaintexts))
+    }
+}